@@ -0,0 +1,370 @@
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::io::BufRead;
+use std::path::{Path, PathBuf};
+use tokio::task::JoinHandle;
+use tracing::{info, warn};
+
+use hydra_core::position::CompletedTrade;
+
+use crate::jsonl::{JsonlWriter, QUEUE_CAPACITY};
+use crate::scorer::AiScore;
+
+/// One scoring call as it happened, successful or not
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuditRecord {
+    pub timestamp: DateTime<Utc>,
+    pub mint_address: String,
+    /// Filled in once a position is opened for this mint, see `AuditLog::link_position`
+    #[serde(default)]
+    pub position_id: Option<String>,
+    pub prompt_version: String,
+    pub model: String,
+    pub prompt: String,
+    pub raw_content: Option<String>,
+    pub score: Option<AiScore>,
+    pub latency_ms: u64,
+    pub error: Option<String>,
+}
+
+/// A line in the audit JSONL file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+enum AuditEntry {
+    Scoring(AuditRecord),
+    Link {
+        timestamp: DateTime<Utc>,
+        mint_address: String,
+        position_id: String,
+    },
+}
+
+/// Append-only JSONL audit log of AI scoring calls.
+/// Writes happen on a background task so the scoring path never blocks on disk.
+#[derive(Clone)]
+pub struct AuditLog {
+    writer: JsonlWriter<AuditEntry>,
+}
+
+impl AuditLog {
+    /// Start the writer task for `path`. The task ends once every
+    /// `AuditLog` handle has been dropped and the queue is drained.
+    pub fn spawn(path: impl Into<PathBuf>) -> (Self, JoinHandle<()>) {
+        let (writer, handle) = JsonlWriter::spawn("ai_audit", path, QUEUE_CAPACITY);
+        (Self { writer }, handle)
+    }
+
+    pub fn record(&self, record: AuditRecord) {
+        self.writer.send(AuditEntry::Scoring(record));
+    }
+
+    /// Tie the most recent scoring record for `mint` to the position opened on it.
+    pub fn link_position(&self, mint: &str, position_id: &str) {
+        self.writer.send(AuditEntry::Link {
+            timestamp: Utc::now(),
+            mint_address: mint.to_string(),
+            position_id: position_id.to_string(),
+        });
+    }
+}
+
+/// Read an audit log back, applying position links to the scoring
+/// record they refer to. Malformed lines are skipped with a warning.
+pub fn load_audit_log(path: impl AsRef<Path>) -> Result<Vec<AuditRecord>> {
+    let path = path.as_ref();
+    let file = std::fs::File::open(path)
+        .with_context(|| format!("Failed to open audit log {}", path.display()))?;
+
+    let mut records: Vec<AuditRecord> = Vec::new();
+    let mut latest_by_mint: HashMap<String, usize> = HashMap::new();
+    for (line_no, line) in std::io::BufReader::new(file).lines().enumerate() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        match serde_json::from_str::<AuditEntry>(&line) {
+            Ok(AuditEntry::Scoring(record)) => {
+                latest_by_mint.insert(record.mint_address.clone(), records.len());
+                records.push(record);
+            }
+            Ok(AuditEntry::Link {
+                mint_address,
+                position_id,
+                ..
+            }) => {
+                if let Some(&idx) = latest_by_mint.get(&mint_address) {
+                    records[idx].position_id = Some(position_id);
+                }
+            }
+            Err(e) => warn!(line = line_no + 1, error = %e, "Skipping malformed audit line"),
+        }
+    }
+    Ok(records)
+}
+
+/// A scoring record and the trade that followed it, if any
+#[derive(Debug, Clone)]
+pub struct JoinedOutcome {
+    pub record: AuditRecord,
+    pub trade: CompletedTrade,
+}
+
+/// Match trades to the scoring record that led to them: by position id
+/// first, otherwise the latest successful record for the mint that
+/// precedes the trade's `opened_at`.
+pub fn join_outcomes(records: &[AuditRecord], trades: &[CompletedTrade]) -> Vec<JoinedOutcome> {
+    let by_position: HashMap<&str, &AuditRecord> = records
+        .iter()
+        .filter_map(|r| r.position_id.as_deref().map(|id| (id, r)))
+        .collect();
+
+    trades
+        .iter()
+        .filter_map(|trade| {
+            let record = by_position
+                .get(trade.position_id.as_str())
+                .copied()
+                .or_else(|| {
+                    records
+                        .iter()
+                        .filter(|r| {
+                            r.mint_address == trade.mint_address
+                                && r.score.is_some()
+                                && r.timestamp <= trade.opened_at
+                        })
+                        .max_by_key(|r| r.timestamp)
+                })?;
+            Some(JoinedOutcome {
+                record: record.clone(),
+                trade: trade.clone(),
+            })
+        })
+        .collect()
+}
+
+/// Realized results of trades whose AI confidence fell in `[lower, upper)`
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConfidenceBucket {
+    pub lower: f64,
+    pub upper: f64,
+    pub trades: usize,
+    pub wins: usize,
+    pub total_pnl_sol: f64,
+}
+
+impl ConfidenceBucket {
+    pub fn win_rate(&self) -> f64 {
+        if self.trades == 0 {
+            return 0.0;
+        }
+        self.wins as f64 / self.trades as f64
+    }
+
+    pub fn avg_pnl_sol(&self) -> f64 {
+        if self.trades == 0 {
+            return 0.0;
+        }
+        self.total_pnl_sol / self.trades as f64
+    }
+}
+
+/// How well `should_buy` predicted a winning trade.
+/// Only scored calls that led to a trade have an outcome, so unless
+/// trades are also taken on `should_buy = false` (shadow or override
+/// entries) the negative cells stay empty and recall is 1.0 by construction.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AuditReport {
+    pub records: usize,
+    pub errors: usize,
+    pub joined: usize,
+    pub true_positives: usize,
+    pub false_positives: usize,
+    pub true_negatives: usize,
+    pub false_negatives: usize,
+    pub buckets: Vec<ConfidenceBucket>,
+}
+
+impl AuditReport {
+    pub fn build(records: &[AuditRecord], trades: &[CompletedTrade], num_buckets: usize) -> Self {
+        let num_buckets = num_buckets.max(1);
+        let width = 1.0 / num_buckets as f64;
+        let mut buckets: Vec<ConfidenceBucket> = (0..num_buckets)
+            .map(|i| ConfidenceBucket {
+                lower: i as f64 * width,
+                upper: (i + 1) as f64 * width,
+                trades: 0,
+                wins: 0,
+                total_pnl_sol: 0.0,
+            })
+            .collect();
+
+        let joined = join_outcomes(records, trades);
+        let (mut tp, mut fp, mut tn, mut fn_) = (0, 0, 0, 0);
+        for outcome in &joined {
+            let Some(score) = &outcome.record.score else {
+                continue;
+            };
+            let win = outcome.trade.pnl_sol > 0.0;
+            match (score.should_buy, win) {
+                (true, true) => tp += 1,
+                (true, false) => fp += 1,
+                (false, false) => tn += 1,
+                (false, true) => fn_ += 1,
+            }
+            let idx = ((score.confidence.clamp(0.0, 1.0) / width) as usize).min(num_buckets - 1);
+            let bucket = &mut buckets[idx];
+            bucket.trades += 1;
+            bucket.total_pnl_sol += outcome.trade.pnl_sol;
+            if win {
+                bucket.wins += 1;
+            }
+        }
+
+        let report = Self {
+            records: records.len(),
+            errors: records.iter().filter(|r| r.error.is_some()).count(),
+            joined: joined.len(),
+            true_positives: tp,
+            false_positives: fp,
+            true_negatives: tn,
+            false_negatives: fn_,
+            buckets,
+        };
+        info!(
+            records = report.records,
+            joined = report.joined,
+            precision = report.precision(),
+            recall = report.recall(),
+            "AI audit report built"
+        );
+        report
+    }
+
+    pub fn precision(&self) -> f64 {
+        ratio(
+            self.true_positives,
+            self.true_positives + self.false_positives,
+        )
+    }
+
+    pub fn recall(&self) -> f64 {
+        ratio(
+            self.true_positives,
+            self.true_positives + self.false_negatives,
+        )
+    }
+}
+
+fn ratio(num: usize, den: usize) -> f64 {
+    if den == 0 {
+        return 0.0;
+    }
+    num as f64 / den as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    fn record(mint: &str, confidence: f64, should_buy: bool, at: DateTime<Utc>) -> AuditRecord {
+        AuditRecord {
+            timestamp: at,
+            mint_address: mint.to_string(),
+            position_id: None,
+            prompt_version: "v1".to_string(),
+            model: "deepseek-chat".to_string(),
+            prompt: "prompt".to_string(),
+            raw_content: Some("{}".to_string()),
+            score: Some(AiScore {
+                confidence,
+                reasoning: String::new(),
+                should_buy,
+            }),
+            latency_ms: 100,
+            error: None,
+        }
+    }
+
+    fn trade(id: &str, mint: &str, pnl: f64, opened_at: DateTime<Utc>) -> CompletedTrade {
+        CompletedTrade {
+            position_id: id.to_string(),
+            mint_address: mint.to_string(),
+            entry_price_usd: 0.001,
+            exit_price_usd: 0.001,
+            size_sol: 1.0,
+            pnl_sol: pnl,
+            opened_at,
+            closed_at: opened_at,
+            exit_reason: "test".to_string(),
+            strategy_id: None,
+        }
+    }
+
+    #[test]
+    fn test_join_prefers_position_id() {
+        let t0 = Utc::now();
+        let mut linked = record("m1", 0.9, true, t0);
+        linked.position_id = Some("p1".to_string());
+        let later = record("m1", 0.2, false, t0 + Duration::seconds(1));
+        let trades = [trade("p1", "m1", 0.5, t0 + Duration::seconds(2))];
+        let joined = join_outcomes(&[linked, later], &trades);
+        assert_eq!(joined.len(), 1);
+        assert!((joined[0].record.score.as_ref().unwrap().confidence - 0.9).abs() < 1e-10);
+    }
+
+    #[test]
+    fn test_join_falls_back_to_latest_prior_record() {
+        let t0 = Utc::now();
+        let records = [
+            record("m1", 0.6, true, t0),
+            record("m1", 0.8, true, t0 + Duration::seconds(1)),
+            record("m1", 0.1, false, t0 + Duration::seconds(10)),
+        ];
+        let trades = [trade("p1", "m1", 0.5, t0 + Duration::seconds(2))];
+        let joined = join_outcomes(&records, &trades);
+        assert!((joined[0].record.score.as_ref().unwrap().confidence - 0.8).abs() < 1e-10);
+    }
+
+    #[test]
+    fn test_report_precision_and_buckets() {
+        let t0 = Utc::now();
+        let mut records = Vec::new();
+        let mut trades = Vec::new();
+        for (i, (conf, pnl)) in [(0.9, 1.0), (0.85, -0.5), (0.3, 0.2)].iter().enumerate() {
+            let mut r = record(&format!("m{i}"), *conf, *conf > 0.5, t0);
+            r.position_id = Some(format!("p{i}"));
+            records.push(r);
+            trades.push(trade(&format!("p{i}"), &format!("m{i}"), *pnl, t0));
+        }
+        let report = AuditReport::build(&records, &trades, 5);
+        assert_eq!(report.joined, 3);
+        assert_eq!(report.true_positives, 1);
+        assert_eq!(report.false_positives, 1);
+        assert_eq!(report.false_negatives, 1);
+        assert!((report.precision() - 0.5).abs() < 1e-10);
+        assert!((report.recall() - 0.5).abs() < 1e-10);
+
+        let top = &report.buckets[4];
+        assert_eq!(top.trades, 2);
+        assert!((top.avg_pnl_sol() - 0.25).abs() < 1e-10);
+    }
+
+    #[tokio::test]
+    async fn test_log_roundtrip_applies_links() {
+        let path = std::env::temp_dir().join(format!("hydra_audit_{}.jsonl", std::process::id()));
+        std::fs::remove_file(&path).ok();
+        let (log, handle) = AuditLog::spawn(&path);
+        log.record(record("m1", 0.7, true, Utc::now()));
+        log.link_position("m1", "pos-1");
+        drop(log);
+        handle.await.unwrap();
+
+        let records = load_audit_log(&path).unwrap();
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].position_id.as_deref(), Some("pos-1"));
+        std::fs::remove_file(&path).ok();
+    }
+}
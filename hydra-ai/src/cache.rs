@@ -1,54 +1,306 @@
+use anyhow::{Context, Result};
 use chrono::{DateTime, Utc};
 use dashmap::DashMap;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Weak};
 use std::time::Duration;
+use tokio::task::JoinHandle;
+use tracing::{debug, info};
+
+use hydra_core::signal::MintSignal;
 
 use crate::scorer::AiScore;
 
+const DEFAULT_MAX_ENTRIES: usize = 10_000;
+
+/// Market state a cached score was computed against
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SignalSnapshot {
+    pub market_cap_usd: f64,
+    pub liquidity_usd: f64,
+    pub holder_count: u64,
+}
+
+impl From<&MintSignal> for SignalSnapshot {
+    fn from(signal: &MintSignal) -> Self {
+        Self {
+            market_cap_usd: signal.market_cap_usd,
+            liquidity_usd: signal.liquidity_usd,
+            holder_count: signal.holder_count,
+        }
+    }
+}
+
+/// A cached score together with the context it was computed in
+#[derive(Debug, Clone)]
+pub struct CachedScore {
+    pub score: AiScore,
+    pub snapshot: Option<SignalSnapshot>,
+    pub inserted_at: DateTime<Utc>,
+}
+
+/// Counters describing cache effectiveness since construction
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CacheStats {
+    pub entries: usize,
+    pub hits: u64,
+    pub misses: u64,
+    /// Entries dropped to stay under the capacity cap
+    pub evictions: u64,
+    /// Entries dropped because their TTL ran out
+    pub expirations: u64,
+}
+
 struct CacheEntry {
     score: AiScore,
+    snapshot: Option<SignalSnapshot>,
+    inserted_at: DateTime<Utc>,
+    last_access: AtomicU64,
+}
+
+/// On-disk representation used by `save_snapshot` / `restore_snapshot`
+#[derive(Serialize, Deserialize)]
+struct PersistedEntry {
+    mint: String,
+    score: AiScore,
+    snapshot: Option<SignalSnapshot>,
     inserted_at: DateTime<Utc>,
 }
 
+/// TTL cache of AI scores keyed by mint, bounded to `max_entries`.
+/// When full, the least recently used entries are evicted in a batch
+/// down to 90% of capacity so eviction cost is amortized.
 pub struct ScoreCache {
     inner: DashMap<String, CacheEntry>,
     ttl: Duration,
+    max_entries: usize,
+    clock: AtomicU64,
+    hits: AtomicU64,
+    misses: AtomicU64,
+    evictions: AtomicU64,
+    expirations: AtomicU64,
 }
 
 impl ScoreCache {
     pub fn new(ttl: Duration) -> Self {
+        Self::with_capacity(ttl, DEFAULT_MAX_ENTRIES)
+    }
+
+    pub fn with_capacity(ttl: Duration, max_entries: usize) -> Self {
         Self {
             inner: DashMap::new(),
             ttl,
+            max_entries: max_entries.max(1),
+            clock: AtomicU64::new(0),
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+            evictions: AtomicU64::new(0),
+            expirations: AtomicU64::new(0),
         }
     }
 
     pub fn get(&self, mint: &str) -> Option<AiScore> {
-        let entry = self.inner.get(mint)?;
-        let age = Utc::now()
-            .signed_duration_since(entry.inserted_at)
-            .to_std()
-            .unwrap_or(Duration::MAX);
-        if age > self.ttl {
+        self.get_entry(mint).map(|entry| entry.score)
+    }
+
+    pub fn get_entry(&self, mint: &str) -> Option<CachedScore> {
+        let Some(entry) = self.inner.get(mint) else {
+            self.misses.fetch_add(1, Ordering::Relaxed);
+            return None;
+        };
+        if self.is_expired(&entry) {
             drop(entry);
-            self.inner.remove(mint);
+            if self
+                .inner
+                .remove_if(mint, |_, e| self.is_expired(e))
+                .is_some()
+            {
+                self.expirations.fetch_add(1, Ordering::Relaxed);
+            }
+            self.misses.fetch_add(1, Ordering::Relaxed);
             return None;
         }
-        Some(entry.score.clone())
+        entry.last_access.store(self.tick(), Ordering::Relaxed);
+        self.hits.fetch_add(1, Ordering::Relaxed);
+        Some(CachedScore {
+            score: entry.score.clone(),
+            snapshot: entry.snapshot.clone(),
+            inserted_at: entry.inserted_at,
+        })
     }
 
     pub fn insert(&self, mint: String, score: AiScore) {
+        self.insert_entry(mint, score, None, Utc::now());
+    }
+
+    /// Insert a score along with the signal it was computed from, so
+    /// re-score triggers can compare against it later.
+    pub fn insert_scored(&self, signal: &MintSignal, score: AiScore) {
+        self.insert_entry(
+            signal.mint_address.clone(),
+            score,
+            Some(SignalSnapshot::from(signal)),
+            Utc::now(),
+        );
+    }
+
+    fn insert_entry(
+        &self,
+        mint: String,
+        score: AiScore,
+        snapshot: Option<SignalSnapshot>,
+        inserted_at: DateTime<Utc>,
+    ) {
         self.inner.insert(
             mint,
             CacheEntry {
                 score,
-                inserted_at: Utc::now(),
+                snapshot,
+                inserted_at,
+                last_access: AtomicU64::new(self.tick()),
             },
         );
+        if self.inner.len() > self.max_entries {
+            self.evict_lru();
+        }
     }
 
     pub fn remove(&self, mint: &str) {
         self.inner.remove(mint);
     }
+
+    pub fn len(&self) -> usize {
+        self.inner.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.inner.is_empty()
+    }
+
+    pub fn stats(&self) -> CacheStats {
+        CacheStats {
+            entries: self.inner.len(),
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+            evictions: self.evictions.load(Ordering::Relaxed),
+            expirations: self.expirations.load(Ordering::Relaxed),
+        }
+    }
+
+    /// Drop every entry whose TTL has run out. Returns how many were removed.
+    pub fn purge_expired(&self) -> usize {
+        let before = self.inner.len();
+        self.inner.retain(|_, e| !self.is_expired(e));
+        let removed = before.saturating_sub(self.inner.len());
+        self.expirations
+            .fetch_add(removed as u64, Ordering::Relaxed);
+        removed
+    }
+
+    /// Spawn a task that purges expired entries every `interval`.
+    /// The task stops on its own once the cache is dropped.
+    pub fn spawn_sweeper(self: &Arc<Self>, interval: Duration) -> JoinHandle<()> {
+        let cache: Weak<Self> = Arc::downgrade(self);
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+            loop {
+                ticker.tick().await;
+                let Some(cache) = cache.upgrade() else {
+                    break;
+                };
+                let removed = cache.purge_expired();
+                if removed > 0 {
+                    debug!(removed, remaining = cache.len(), "Score cache sweep");
+                }
+            }
+        })
+    }
+
+    /// Write all live entries to `path` as JSON.
+    pub fn save_snapshot(&self, path: impl AsRef<Path>) -> Result<usize> {
+        let path = path.as_ref();
+        let entries: Vec<PersistedEntry> = self
+            .inner
+            .iter()
+            .filter(|e| !self.is_expired(e.value()))
+            .map(|e| PersistedEntry {
+                mint: e.key().clone(),
+                score: e.score.clone(),
+                snapshot: e.snapshot.clone(),
+                inserted_at: e.inserted_at,
+            })
+            .collect();
+
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let tmp = path.with_extension("tmp");
+        let json = serde_json::to_vec(&entries)?;
+        std::fs::write(&tmp, json)
+            .with_context(|| format!("Failed to write cache snapshot {}", tmp.display()))?;
+        std::fs::rename(&tmp, path)?;
+
+        info!(entries = entries.len(), path = %path.display(), "Score cache snapshot saved");
+        Ok(entries.len())
+    }
+
+    /// Load entries written by `save_snapshot`, keeping their original
+    /// insertion time so TTLs carry over. Expired entries are skipped.
+    pub fn restore_snapshot(&self, path: impl AsRef<Path>) -> Result<usize> {
+        let path = path.as_ref();
+        let data = std::fs::read(path)
+            .with_context(|| format!("Failed to read cache snapshot {}", path.display()))?;
+        let entries: Vec<PersistedEntry> =
+            serde_json::from_slice(&data).context("Failed to parse cache snapshot")?;
+
+        let mut restored = 0;
+        for entry in entries {
+            if self.age(entry.inserted_at) > self.ttl {
+                continue;
+            }
+            self.insert_entry(entry.mint, entry.score, entry.snapshot, entry.inserted_at);
+            restored += 1;
+        }
+        info!(restored, path = %path.display(), "Score cache snapshot restored");
+        Ok(restored)
+    }
+
+    fn evict_lru(&self) {
+        let target = self.max_entries - self.max_entries / 10;
+        let mut by_access: Vec<(String, u64)> = self
+            .inner
+            .iter()
+            .map(|e| (e.key().clone(), e.last_access.load(Ordering::Relaxed)))
+            .collect();
+        if by_access.len() <= target {
+            return;
+        }
+        by_access.sort_unstable_by_key(|(_, access)| *access);
+        let excess = by_access.len() - target;
+        for (mint, _) in by_access.into_iter().take(excess) {
+            if self.inner.remove(&mint).is_some() {
+                self.evictions.fetch_add(1, Ordering::Relaxed);
+            }
+        }
+    }
+
+    fn tick(&self) -> u64 {
+        self.clock.fetch_add(1, Ordering::Relaxed)
+    }
+
+    fn age(&self, inserted_at: DateTime<Utc>) -> Duration {
+        Utc::now()
+            .signed_duration_since(inserted_at)
+            .to_std()
+            .unwrap_or(Duration::ZERO)
+    }
+
+    fn is_expired(&self, entry: &CacheEntry) -> bool {
+        self.age(entry.inserted_at) > self.ttl
+    }
 }
 
 impl Default for ScoreCache {
@@ -56,3 +308,87 @@ impl Default for ScoreCache {
         Self::new(Duration::from_secs(300))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn score(confidence: f64) -> AiScore {
+        AiScore {
+            confidence,
+            reasoning: "test".to_string(),
+            should_buy: confidence > 0.5,
+        }
+    }
+
+    #[test]
+    fn test_hits_and_misses_counted() {
+        let cache = ScoreCache::default();
+        cache.insert("a".to_string(), score(0.9));
+        assert!(cache.get("a").is_some());
+        assert!(cache.get("b").is_none());
+        let stats = cache.stats();
+        assert_eq!(stats.hits, 1);
+        assert_eq!(stats.misses, 1);
+        assert_eq!(stats.entries, 1);
+    }
+
+    #[test]
+    fn test_capacity_evicts_least_recently_used() {
+        let cache = ScoreCache::with_capacity(Duration::from_secs(60), 10);
+        for i in 0..10 {
+            cache.insert(format!("m{i}"), score(0.5));
+        }
+        // touch m0 so it becomes most recently used
+        assert!(cache.get("m0").is_some());
+        cache.insert("m10".to_string(), score(0.5));
+
+        // 11 entries > 10 → evict down to 9
+        assert_eq!(cache.len(), 9);
+        assert_eq!(cache.stats().evictions, 2);
+        assert!(cache.get("m0").is_some());
+        assert!(cache.get("m1").is_none());
+        assert!(cache.get("m2").is_none());
+        assert!(cache.get("m10").is_some());
+    }
+
+    #[test]
+    fn test_purge_expired() {
+        let cache = ScoreCache::new(Duration::ZERO);
+        cache.insert("a".to_string(), score(0.9));
+        std::thread::sleep(Duration::from_millis(5));
+        assert_eq!(cache.purge_expired(), 1);
+        assert!(cache.is_empty());
+        assert_eq!(cache.stats().expirations, 1);
+    }
+
+    #[test]
+    fn test_snapshot_roundtrip() {
+        let path =
+            std::env::temp_dir().join(format!("hydra_score_cache_{}.json", std::process::id()));
+        let cache = ScoreCache::default();
+        cache.insert("a".to_string(), score(0.9));
+        cache.insert("b".to_string(), score(0.2));
+        assert_eq!(cache.save_snapshot(&path).unwrap(), 2);
+
+        let restored = ScoreCache::default();
+        assert_eq!(restored.restore_snapshot(&path).unwrap(), 2);
+        let a = restored.get("a").unwrap();
+        assert!((a.confidence - 0.9).abs() < 1e-10);
+        std::fs::remove_file(&path).ok();
+    }
+
+    #[tokio::test]
+    async fn test_sweeper_purges_in_background() {
+        let cache = Arc::new(ScoreCache::new(Duration::from_millis(10)));
+        cache.insert("a".to_string(), score(0.9));
+        let handle = cache.spawn_sweeper(Duration::from_millis(20));
+        tokio::time::sleep(Duration::from_millis(80)).await;
+        assert!(cache.is_empty());
+        drop(cache);
+        tokio::time::timeout(Duration::from_secs(1), handle)
+            .await
+            .unwrap()
+            .unwrap();
+    }
+}
@@ -0,0 +1,448 @@
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+use std::fmt::Write as _;
+use std::path::Path;
+use tracing::info;
+
+use hydra_core::signal::MintSignal;
+use hydra_core::traits::{AiAnalyzer, ScoredSignal};
+
+use crate::audit::JoinedOutcome;
+use crate::scorer::{AiScore, Scorer};
+
+const PLATT_MAX_ITERATIONS: usize = 100;
+const PLATT_TOLERANCE: f64 = 1e-10;
+
+/// Maps a raw LLM confidence onto an empirical win probability.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "method", rename_all = "snake_case")]
+pub enum Calibrator {
+    /// Pass confidences through unchanged
+    #[default]
+    Identity,
+    /// `p = 1 / (1 + exp(-(a * confidence + b)))`
+    Platt { a: f64, b: f64 },
+    /// Monotone piecewise-linear map through `(confidence, probability)` knots
+    Isotonic { points: Vec<(f64, f64)> },
+}
+
+impl Calibrator {
+    /// Fit Platt scaling by Newton's method on the log-loss, using
+    /// Platt's smoothed targets to avoid overconfident fits on small samples.
+    pub fn fit_platt(samples: &[(f64, bool)]) -> Result<Self> {
+        let positives = samples.iter().filter(|(_, win)| *win).count();
+        let negatives = samples.len() - positives;
+        if positives == 0 || negatives == 0 {
+            bail!(
+                "Platt scaling needs both wins and losses ({positives} wins, {negatives} losses)"
+            );
+        }
+        let hi = (positives as f64 + 1.0) / (positives as f64 + 2.0);
+        let lo = 1.0 / (negatives as f64 + 2.0);
+
+        let (mut a, mut b) = (1.0, 0.0);
+        for _ in 0..PLATT_MAX_ITERATIONS {
+            // gradient and Hessian of the negative log-likelihood
+            let (mut ga, mut gb, mut haa, mut hab, mut hbb) = (0.0, 0.0, 0.0, 0.0, 0.0);
+            for &(x, win) in samples {
+                let t = if win { hi } else { lo };
+                let p = sigmoid(a * x + b);
+                let d = p - t;
+                let w = (p * (1.0 - p)).max(1e-12);
+                ga += d * x;
+                gb += d;
+                haa += w * x * x;
+                hab += w * x;
+                hbb += w;
+            }
+            let det = haa * hbb - hab * hab;
+            if det.abs() < 1e-18 {
+                break;
+            }
+            let da = (hbb * ga - hab * gb) / det;
+            let db = (haa * gb - hab * ga) / det;
+            a -= da;
+            b -= db;
+            if da.abs() < PLATT_TOLERANCE && db.abs() < PLATT_TOLERANCE {
+                break;
+            }
+        }
+        if !a.is_finite() || !b.is_finite() {
+            bail!("Platt scaling did not converge");
+        }
+        info!(a, b, samples = samples.len(), "Fitted Platt calibration");
+        Ok(Calibrator::Platt { a, b })
+    }
+
+    /// Fit isotonic regression with the pool-adjacent-violators algorithm.
+    /// Non-finite confidences are dropped.
+    pub fn fit_isotonic(samples: &[(f64, bool)]) -> Result<Self> {
+        let mut sorted: Vec<(f64, f64)> = samples
+            .iter()
+            .filter(|(x, _)| x.is_finite())
+            .map(|&(x, win)| (x.clamp(0.0, 1.0), if win { 1.0 } else { 0.0 }))
+            .collect();
+        if sorted.is_empty() {
+            bail!("Isotonic calibration needs at least one finite sample");
+        }
+        sorted.sort_by(|l, r| l.0.total_cmp(&r.0));
+
+        // Equal confidences are one observation, or their order would
+        // decide the fit: (x, sum_y, weight) per distinct x
+        let mut pooled: Vec<(f64, f64, f64)> = Vec::with_capacity(sorted.len());
+        for (x, y) in sorted {
+            match pooled.last_mut() {
+                Some(last) if last.0 == x => {
+                    last.1 += y;
+                    last.2 += 1.0;
+                }
+                _ => pooled.push((x, y, 1.0)),
+            }
+        }
+
+        // (sum_x, sum_y, weight) per block
+        let mut blocks: Vec<(f64, f64, f64)> = Vec::with_capacity(pooled.len());
+        for (x, sy, w) in pooled {
+            blocks.push((x * w, sy, w));
+            while blocks.len() > 1 {
+                let n = blocks.len();
+                let (prev, last) = (blocks[n - 2], blocks[n - 1]);
+                if prev.1 / prev.2 <= last.1 / last.2 {
+                    break;
+                }
+                blocks.truncate(n - 2);
+                blocks.push((prev.0 + last.0, prev.1 + last.1, prev.2 + last.2));
+            }
+        }
+        let points = blocks
+            .into_iter()
+            .map(|(sx, sy, w)| (sx / w, sy / w))
+            .collect::<Vec<_>>();
+        info!(
+            knots = points.len(),
+            samples = samples.len(),
+            "Fitted isotonic calibration"
+        );
+        Ok(Calibrator::Isotonic { points })
+    }
+
+    /// A non-finite confidence maps to 0, never to a likely win
+    pub fn apply(&self, confidence: f64) -> f64 {
+        if !confidence.is_finite() {
+            return 0.0;
+        }
+        let x = confidence.clamp(0.0, 1.0);
+        match self {
+            Calibrator::Identity => x,
+            Calibrator::Platt { a, b } => sigmoid(a * x + b),
+            Calibrator::Isotonic { points } => interpolate(points, x),
+        }
+    }
+
+    /// Replace the score's confidence with the calibrated probability.
+    pub fn calibrate(&self, mut score: AiScore) -> AiScore {
+        score.confidence = self.apply(score.confidence);
+        score
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<()> {
+        let path = path.as_ref();
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(path, serde_json::to_vec_pretty(self)?)
+            .with_context(|| format!("Failed to write calibration {}", path.display()))
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let data = std::fs::read(path)
+            .with_context(|| format!("Failed to read calibration {}", path.display()))?;
+        let calibrator: Self =
+            serde_json::from_slice(&data).context("Failed to parse calibration")?;
+        calibrator.validate()?;
+        Ok(calibrator)
+    }
+
+    /// Parameters must be finite and knots sorted by confidence, as a
+    /// fit leaves them
+    pub fn validate(&self) -> Result<()> {
+        match self {
+            Calibrator::Identity => {}
+            Calibrator::Platt { a, b } => {
+                if !a.is_finite() || !b.is_finite() {
+                    bail!("Platt parameters must be finite (a={a}, b={b})");
+                }
+            }
+            Calibrator::Isotonic { points } => {
+                if points.iter().any(|(x, y)| !x.is_finite() || !y.is_finite()) {
+                    bail!("Isotonic knots must be finite");
+                }
+                if points.windows(2).any(|w| w[0].0 > w[1].0) {
+                    bail!("Isotonic knots must be sorted by confidence");
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+fn sigmoid(z: f64) -> f64 {
+    1.0 / (1.0 + (-z).exp())
+}
+
+fn interpolate(points: &[(f64, f64)], x: f64) -> f64 {
+    let (Some(first), Some(last)) = (points.first(), points.last()) else {
+        return x;
+    };
+    if x <= first.0 {
+        return first.1;
+    }
+    if x >= last.0 {
+        return last.1;
+    }
+    let idx = points.partition_point(|p| p.0 <= x);
+    let (x0, y0) = points[idx - 1];
+    let (x1, y1) = points[idx];
+    if x1 == x0 {
+        return y1;
+    }
+    y0 + (y1 - y0) * (x - x0) / (x1 - x0)
+}
+
+/// `(raw confidence, won)` pairs from scoring records joined to trades
+pub fn samples_from_outcomes(outcomes: &[JoinedOutcome]) -> Vec<(f64, bool)> {
+    outcomes
+        .iter()
+        .filter_map(|o| {
+            o.record
+                .score
+                .as_ref()
+                .map(|s| (s.confidence, o.trade.pnl_sol > 0.0))
+        })
+        .collect()
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReliabilityBin {
+    pub lower: f64,
+    pub upper: f64,
+    pub count: usize,
+    pub mean_predicted: f64,
+    pub observed_rate: f64,
+}
+
+/// Reliability diagram data: predicted probability against observed win
+/// rate per bin, plus expected calibration error and Brier score.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReliabilityReport {
+    pub bins: Vec<ReliabilityBin>,
+    pub expected_calibration_error: f64,
+    pub brier_score: f64,
+}
+
+impl ReliabilityReport {
+    pub fn build(samples: &[(f64, bool)], calibrator: &Calibrator, num_bins: usize) -> Self {
+        let num_bins = num_bins.max(1);
+        let width = 1.0 / num_bins as f64;
+        let mut sums = vec![(0usize, 0.0f64, 0usize); num_bins];
+        let mut brier = 0.0;
+        for &(raw, win) in samples {
+            let p = calibrator.apply(raw);
+            let y = if win { 1.0 } else { 0.0 };
+            brier += (p - y) * (p - y);
+            let idx = ((p / width) as usize).min(num_bins - 1);
+            sums[idx].0 += 1;
+            sums[idx].1 += p;
+            sums[idx].2 += win as usize;
+        }
+
+        let total = samples.len().max(1) as f64;
+        let mut ece = 0.0;
+        let bins = sums
+            .into_iter()
+            .enumerate()
+            .map(|(i, (count, sum_p, wins))| {
+                let (mean_predicted, observed_rate) = if count > 0 {
+                    (sum_p / count as f64, wins as f64 / count as f64)
+                } else {
+                    (0.0, 0.0)
+                };
+                ece += count as f64 / total * (mean_predicted - observed_rate).abs();
+                ReliabilityBin {
+                    lower: i as f64 * width,
+                    upper: (i + 1) as f64 * width,
+                    count,
+                    mean_predicted,
+                    observed_rate,
+                }
+            })
+            .collect();
+
+        Self {
+            bins,
+            expected_calibration_error: ece,
+            brier_score: brier / total,
+        }
+    }
+
+    /// Plain-text reliability diagram, one row per non-empty bin.
+    pub fn render(&self) -> String {
+        let mut out = String::from("bin          n     predicted  observed\n");
+        for bin in self.bins.iter().filter(|b| b.count > 0) {
+            let bar = "#".repeat((bin.observed_rate * 20.0).round() as usize);
+            let _ = writeln!(
+                out,
+                "[{:.2},{:.2})  {:<5} {:>9.3}  {:>8.3}  {}",
+                bin.lower, bin.upper, bin.count, bin.mean_predicted, bin.observed_rate, bar
+            );
+        }
+        let _ = writeln!(
+            out,
+            "ECE={:.4} Brier={:.4}",
+            self.expected_calibration_error, self.brier_score
+        );
+        out
+    }
+}
+
+/// Applies a `Calibrator` to every score from the wrapped scorer before
+/// it reaches strategy code.
+pub struct CalibratedScorer<S> {
+    inner: S,
+    calibrator: Calibrator,
+}
+
+impl<S: Scorer> CalibratedScorer<S> {
+    pub fn new(inner: S, calibrator: Calibrator) -> Self {
+        Self { inner, calibrator }
+    }
+
+    pub fn calibrator(&self) -> &Calibrator {
+        &self.calibrator
+    }
+}
+
+impl<S: Scorer> Scorer for CalibratedScorer<S> {
+    async fn score(&self, signal: &MintSignal) -> Result<AiScore> {
+        let raw = self.inner.score(signal).await?;
+        Ok(self.calibrator.calibrate(raw))
+    }
+}
+
+impl<S: Scorer> AiAnalyzer for CalibratedScorer<S> {
+    async fn analyze(&self, signal: &MintSignal) -> Option<ScoredSignal> {
+        let score = Scorer::score(self, signal).await.ok()?;
+        Some(ScoredSignal {
+            signal: signal.clone(),
+            score: score.confidence,
+            should_buy: score.should_buy,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Overconfident model: says 0.9 but wins 30%, says 0.5 and wins 10%.
+    fn overconfident() -> Vec<(f64, bool)> {
+        let mut samples = Vec::new();
+        for i in 0..100 {
+            samples.push((0.9, i % 10 < 3));
+            samples.push((0.5, i % 10 < 1));
+        }
+        samples
+    }
+
+    #[test]
+    fn test_isotonic_recovers_rates() {
+        let cal = Calibrator::fit_isotonic(&overconfident()).unwrap();
+        assert!((cal.apply(0.9) - 0.3).abs() < 1e-9);
+        assert!((cal.apply(0.5) - 0.1).abs() < 1e-9);
+        // interpolated and monotone in between
+        let mid = cal.apply(0.7);
+        assert!(mid > 0.1 && mid < 0.3);
+    }
+
+    #[test]
+    fn test_isotonic_pools_violators() {
+        let samples = [(0.2, true), (0.4, false), (0.6, true), (0.8, true)];
+        let cal = Calibrator::fit_isotonic(&samples).unwrap();
+        let Calibrator::Isotonic { points } = &cal else {
+            panic!("expected isotonic");
+        };
+        assert!(points.windows(2).all(|w| w[0].1 <= w[1].1));
+        assert!((cal.apply(0.3) - 0.5).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_isotonic_pools_tied_confidences() {
+        let samples = [(0.5, false), (0.5, true), (0.5, true), (0.9, true)];
+        let cal = Calibrator::fit_isotonic(&samples).unwrap();
+        assert!((cal.apply(0.5) - 2.0 / 3.0).abs() < 1e-9);
+        assert!((cal.apply(0.9) - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_non_finite_confidences() {
+        let samples = [(f64::NAN, true), (0.2, false), (0.8, true)];
+        let cal = Calibrator::fit_isotonic(&samples).unwrap();
+        let Calibrator::Isotonic { points } = &cal else {
+            panic!("expected isotonic");
+        };
+        assert_eq!(points.len(), 2);
+        assert_eq!(cal.apply(f64::NAN), 0.0);
+        assert_eq!(cal.apply(f64::INFINITY), 0.0);
+        assert!(Calibrator::fit_isotonic(&[(f64::NAN, true)]).is_err());
+    }
+
+    #[test]
+    fn test_load_rejects_bad_knots() {
+        let path =
+            std::env::temp_dir().join(format!("hydra_calibration_bad_{}.json", std::process::id()));
+        Calibrator::Isotonic {
+            points: vec![(0.8, 0.5), (0.2, 0.1)],
+        }
+        .save(&path)
+        .unwrap();
+        assert!(Calibrator::load(&path).is_err());
+        std::fs::remove_file(&path).ok();
+    }
+
+    #[test]
+    fn test_platt_improves_calibration() {
+        let samples = overconfident();
+        let cal = Calibrator::fit_platt(&samples).unwrap();
+        assert!(cal.apply(0.9) < 0.5);
+        assert!(cal.apply(0.9) > cal.apply(0.5));
+
+        let raw = ReliabilityReport::build(&samples, &Calibrator::Identity, 10);
+        let fitted = ReliabilityReport::build(&samples, &cal, 10);
+        assert!(fitted.brier_score < raw.brier_score);
+        assert!(fitted.expected_calibration_error < raw.expected_calibration_error);
+    }
+
+    #[test]
+    fn test_platt_needs_both_classes() {
+        assert!(Calibrator::fit_platt(&[(0.5, true), (0.7, true)]).is_err());
+    }
+
+    #[test]
+    fn test_save_and_load() {
+        let path =
+            std::env::temp_dir().join(format!("hydra_calibration_{}.json", std::process::id()));
+        let cal = Calibrator::Platt { a: 2.0, b: -1.0 };
+        cal.save(&path).unwrap();
+        assert_eq!(Calibrator::load(&path).unwrap(), cal);
+        std::fs::remove_file(&path).ok();
+    }
+
+    #[test]
+    fn test_render_lists_bins() {
+        let report = ReliabilityReport::build(&overconfident(), &Calibrator::Identity, 10);
+        let text = report.render();
+        assert!(text.contains("[0.90,1.00)"));
+        assert!(text.contains("ECE="));
+    }
+}
@@ -0,0 +1,287 @@
+use anyhow::{anyhow, Result};
+use dashmap::mapref::entry::Entry;
+use dashmap::DashMap;
+use std::future::Future;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+use tokio::sync::watch;
+use tracing::debug;
+
+use hydra_core::signal::MintSignal;
+
+use crate::cache::{CachedScore, ScoreCache};
+use crate::scorer::{AiScore, Scorer};
+
+type FlightResult<T> = Option<Result<T, String>>;
+
+/// Deduplicates concurrent calls that share a key.
+/// The first caller runs the future; everyone arriving while it is in
+/// flight awaits the same result instead of issuing their own call.
+pub struct SingleFlight<T> {
+    inflight: DashMap<String, watch::Receiver<FlightResult<T>>>,
+    coalesced: AtomicU64,
+}
+
+/// Removes the in-flight entry even if the leader future is dropped.
+struct FlightGuard<'a, T> {
+    inflight: &'a DashMap<String, watch::Receiver<FlightResult<T>>>,
+    key: &'a str,
+}
+
+impl<T> Drop for FlightGuard<'_, T> {
+    fn drop(&mut self) {
+        self.inflight.remove(self.key);
+    }
+}
+
+impl<T: Clone + Send + Sync> SingleFlight<T> {
+    pub fn new() -> Self {
+        Self {
+            inflight: DashMap::new(),
+            coalesced: AtomicU64::new(0),
+        }
+    }
+
+    pub async fn run<F, Fut>(&self, key: &str, f: F) -> Result<T>
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Result<T>>,
+    {
+        let mut f = Some(f);
+        loop {
+            let joined = match self.inflight.entry(key.to_string()) {
+                Entry::Occupied(e) => Err(e.get().clone()),
+                Entry::Vacant(e) => {
+                    let (tx, rx) = watch::channel(None);
+                    e.insert(rx);
+                    Ok(tx)
+                }
+            };
+
+            match joined {
+                Ok(tx) => {
+                    let _guard = FlightGuard {
+                        inflight: &self.inflight,
+                        key,
+                    };
+                    let Some(f) = f.take() else {
+                        return Err(anyhow!("single-flight leader already ran for {key}"));
+                    };
+                    let result = f().await;
+                    let shared = match &result {
+                        Ok(value) => Ok(value.clone()),
+                        Err(e) => Err(format!("{e:#}")),
+                    };
+                    // Receivers may all be gone already; that is fine.
+                    let _ = tx.send(Some(shared));
+                    return result;
+                }
+                Err(mut rx) => {
+                    self.coalesced.fetch_add(1, Ordering::Relaxed);
+                    debug!(key, "Joining in-flight request");
+                    match rx.wait_for(|v| v.is_some()).await {
+                        Ok(value) => {
+                            return match value.clone() {
+                                Some(Ok(v)) => Ok(v),
+                                Some(Err(e)) => Err(anyhow!(e)),
+                                None => Err(anyhow!("single-flight result missing for {key}")),
+                            };
+                        }
+                        // Leader was cancelled before finishing; try again.
+                        Err(_) => continue,
+                    }
+                }
+            }
+        }
+    }
+
+    /// Number of callers that joined an existing flight instead of starting one
+    pub fn coalesced(&self) -> u64 {
+        self.coalesced.load(Ordering::Relaxed)
+    }
+
+    pub fn in_flight(&self) -> usize {
+        self.inflight.len()
+    }
+}
+
+impl<T: Clone + Send + Sync> Default for SingleFlight<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Condition under which a cached score is considered stale
+#[derive(Debug, Clone)]
+pub enum RescoreTrigger {
+    /// Market cap moved more than this many percent since scoring
+    McapMovePct(f64),
+    /// Liquidity moved more than this many percent since scoring
+    LiquidityMovePct(f64),
+    /// Holder count grew by at least this many since scoring
+    HolderGrowth(u64),
+    /// Score is older than this, regardless of the cache TTL
+    MaxAge(Duration),
+}
+
+impl RescoreTrigger {
+    fn fires(&self, cached: &CachedScore, signal: &MintSignal) -> bool {
+        let snapshot = cached.snapshot.as_ref();
+        match *self {
+            RescoreTrigger::McapMovePct(pct) => {
+                snapshot.is_some_and(|s| pct_move(s.market_cap_usd, signal.market_cap_usd) > pct)
+            }
+            RescoreTrigger::LiquidityMovePct(pct) => {
+                snapshot.is_some_and(|s| pct_move(s.liquidity_usd, signal.liquidity_usd) > pct)
+            }
+            RescoreTrigger::HolderGrowth(n) => {
+                snapshot.is_some_and(|s| signal.holder_count.saturating_sub(s.holder_count) >= n)
+            }
+            RescoreTrigger::MaxAge(max) => chrono::Utc::now()
+                .signed_duration_since(cached.inserted_at)
+                .to_std()
+                .is_ok_and(|age| age > max),
+        }
+    }
+}
+
+fn pct_move(from: f64, to: f64) -> f64 {
+    if from == 0.0 {
+        return if to == 0.0 { 0.0 } else { f64::INFINITY };
+    }
+    ((to - from) / from).abs() * 100.0
+}
+
+/// Scorer wrapper that serves fresh cache hits, coalesces concurrent
+/// misses per mint and only caches successful results.
+pub struct CoalescingScorer<S> {
+    inner: S,
+    cache: ScoreCache,
+    flights: SingleFlight<AiScore>,
+    triggers: Vec<RescoreTrigger>,
+}
+
+impl<S: Scorer> CoalescingScorer<S> {
+    pub fn new(inner: S, cache: ScoreCache, triggers: Vec<RescoreTrigger>) -> Self {
+        Self {
+            inner,
+            cache,
+            flights: SingleFlight::new(),
+            triggers,
+        }
+    }
+
+    pub fn cache(&self) -> &ScoreCache {
+        &self.cache
+    }
+
+    pub fn flights(&self) -> &SingleFlight<AiScore> {
+        &self.flights
+    }
+
+    fn needs_rescore(&self, cached: &CachedScore, signal: &MintSignal) -> bool {
+        self.triggers.iter().any(|t| t.fires(cached, signal))
+    }
+}
+
+impl<S: Scorer> Scorer for CoalescingScorer<S> {
+    async fn score(&self, signal: &MintSignal) -> Result<AiScore> {
+        if let Some(cached) = self.cache.get_entry(&signal.mint_address) {
+            if !self.needs_rescore(&cached, signal) {
+                return Ok(cached.score);
+            }
+            debug!(mint = %signal.mint_address, "Re-score trigger fired");
+        }
+
+        self.flights
+            .run(&signal.mint_address, || async {
+                let score = self.inner.score(signal).await?;
+                self.cache.insert_scored(signal, score.clone());
+                Ok(score)
+            })
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+
+    struct CountingScorer {
+        calls: AtomicU64,
+        fail: bool,
+    }
+
+    impl Scorer for CountingScorer {
+        async fn score(&self, _signal: &MintSignal) -> Result<AiScore> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            tokio::time::sleep(Duration::from_millis(50)).await;
+            if self.fail {
+                anyhow::bail!("upstream failed");
+            }
+            Ok(AiScore {
+                confidence: 0.8,
+                reasoning: "ok".to_string(),
+                should_buy: true,
+            })
+        }
+    }
+
+    fn scorer(fail: bool, triggers: Vec<RescoreTrigger>) -> Arc<CoalescingScorer<CountingScorer>> {
+        Arc::new(CoalescingScorer::new(
+            CountingScorer {
+                calls: AtomicU64::new(0),
+                fail,
+            },
+            ScoreCache::default(),
+            triggers,
+        ))
+    }
+
+    fn signal(mcap: f64) -> MintSignal {
+        MintSignal::new("mint_a".to_string(), mcap, 0.0, 0.001, 10, 1_000.0, 5.0)
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_calls_share_one_request() {
+        let s = scorer(false, vec![]);
+        let mut handles = Vec::new();
+        for _ in 0..8 {
+            let s = Arc::clone(&s);
+            handles.push(tokio::spawn(async move { s.score(&signal(1_000.0)).await }));
+        }
+        for h in handles {
+            assert!(h.await.unwrap().is_ok());
+        }
+        assert_eq!(s.inner.calls.load(Ordering::SeqCst), 1);
+        assert_eq!(s.flights().coalesced(), 7);
+        assert_eq!(s.flights().in_flight(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_failure_is_shared_but_not_cached() {
+        let s = scorer(true, vec![]);
+        let sig = signal(1_000.0);
+        let (a, b) = tokio::join!(s.score(&sig), s.score(&sig));
+        assert!(a.is_err());
+        assert!(b.is_err());
+        assert_eq!(s.inner.calls.load(Ordering::SeqCst), 1);
+        assert!(s.cache().get("mint_a").is_none());
+
+        assert!(s.score(&signal(1_000.0)).await.is_err());
+        assert_eq!(s.inner.calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_mcap_move_triggers_rescore() {
+        let s = scorer(false, vec![RescoreTrigger::McapMovePct(20.0)]);
+        s.score(&signal(1_000.0)).await.unwrap();
+        // +10% stays cached
+        s.score(&signal(1_100.0)).await.unwrap();
+        assert_eq!(s.inner.calls.load(Ordering::SeqCst), 1);
+        // +50% re-scores
+        s.score(&signal(1_500.0)).await.unwrap();
+        assert_eq!(s.inner.calls.load(Ordering::SeqCst), 2);
+    }
+}
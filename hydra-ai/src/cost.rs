@@ -0,0 +1,193 @@
+use chrono::{NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Mutex;
+use tracing::{info, warn};
+
+/// Token usage as reported in the `usage` field of a chat completion
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Usage {
+    #[serde(default)]
+    pub prompt_tokens: u32,
+    #[serde(default)]
+    pub completion_tokens: u32,
+    #[serde(default)]
+    pub total_tokens: u32,
+}
+
+/// Price of a model in USD per million tokens
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ModelPrice {
+    pub input_per_mtok_usd: f64,
+    pub output_per_mtok_usd: f64,
+}
+
+impl ModelPrice {
+    pub fn new(input_per_mtok_usd: f64, output_per_mtok_usd: f64) -> Self {
+        Self {
+            input_per_mtok_usd,
+            output_per_mtok_usd,
+        }
+    }
+
+    pub fn cost_usd(&self, usage: &Usage) -> f64 {
+        (usage.prompt_tokens as f64 * self.input_per_mtok_usd
+            + usage.completion_tokens as f64 * self.output_per_mtok_usd)
+            / 1_000_000.0
+    }
+}
+
+/// Running totals for one UTC day
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CostSnapshot {
+    pub requests: u64,
+    pub prompt_tokens: u64,
+    pub completion_tokens: u64,
+    pub cost_usd: f64,
+}
+
+struct CostState {
+    day: NaiveDate,
+    today: CostSnapshot,
+    lifetime: CostSnapshot,
+}
+
+/// Accumulates LLM spend against per-model prices and an optional
+/// daily budget. Resets at UTC midnight like `DailyLimits`.
+pub struct CostTracker {
+    prices: HashMap<String, ModelPrice>,
+    daily_budget_usd: Option<f64>,
+    state: Mutex<CostState>,
+}
+
+impl CostTracker {
+    pub fn new(prices: HashMap<String, ModelPrice>, daily_budget_usd: Option<f64>) -> Self {
+        Self {
+            prices,
+            daily_budget_usd,
+            state: Mutex::new(CostState {
+                day: Utc::now().date_naive(),
+                today: CostSnapshot::default(),
+                lifetime: CostSnapshot::default(),
+            }),
+        }
+    }
+
+    /// DeepSeek list prices (cache-miss input) at the time of writing.
+    pub fn default_prices() -> HashMap<String, ModelPrice> {
+        HashMap::from([
+            ("deepseek-chat".to_string(), ModelPrice::new(0.27, 1.10)),
+            ("deepseek-reasoner".to_string(), ModelPrice::new(0.55, 2.19)),
+        ])
+    }
+
+    /// Record a completed call and return its cost in USD.
+    pub fn record(&self, model: &str, usage: &Usage) -> f64 {
+        let cost = match self.prices.get(model) {
+            Some(price) => price.cost_usd(usage),
+            None => {
+                warn!(model, "No price configured for model, cost recorded as 0");
+                0.0
+            }
+        };
+        let mut guard = self.lock();
+        let state = &mut *guard;
+        Self::maybe_reset(state);
+        for totals in [&mut state.today, &mut state.lifetime] {
+            totals.requests += 1;
+            totals.prompt_tokens += usage.prompt_tokens as u64;
+            totals.completion_tokens += usage.completion_tokens as u64;
+            totals.cost_usd += cost;
+        }
+        cost
+    }
+
+    /// True once today's spend has reached the daily budget.
+    pub fn is_exhausted(&self) -> bool {
+        let Some(budget) = self.daily_budget_usd else {
+            return false;
+        };
+        let mut state = self.lock();
+        Self::maybe_reset(&mut state);
+        state.today.cost_usd >= budget
+    }
+
+    pub fn remaining_budget_usd(&self) -> Option<f64> {
+        let budget = self.daily_budget_usd?;
+        Some((budget - self.today().cost_usd).max(0.0))
+    }
+
+    pub fn today(&self) -> CostSnapshot {
+        let mut state = self.lock();
+        Self::maybe_reset(&mut state);
+        state.today.clone()
+    }
+
+    pub fn lifetime(&self) -> CostSnapshot {
+        self.lock().lifetime.clone()
+    }
+
+    fn maybe_reset(state: &mut CostState) {
+        let today = Utc::now().date_naive();
+        if today != state.day {
+            info!(
+                day = %state.day,
+                cost_usd = state.today.cost_usd,
+                requests = state.today.requests,
+                "AI cost totals reset for new day"
+            );
+            state.day = today;
+            state.today = CostSnapshot::default();
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, CostState> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl Default for CostTracker {
+    fn default() -> Self {
+        Self::new(Self::default_prices(), None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn usage(prompt: u32, completion: u32) -> Usage {
+        Usage {
+            prompt_tokens: prompt,
+            completion_tokens: completion,
+            total_tokens: prompt + completion,
+        }
+    }
+
+    #[test]
+    fn test_cost_per_model() {
+        let tracker = CostTracker::default();
+        // 1M in at 0.27 + 1M out at 1.10
+        let cost = tracker.record("deepseek-chat", &usage(1_000_000, 1_000_000));
+        assert!((cost - 1.37).abs() < 1e-9);
+        let today = tracker.today();
+        assert_eq!(today.requests, 1);
+        assert_eq!(today.prompt_tokens, 1_000_000);
+    }
+
+    #[test]
+    fn test_unknown_model_costs_nothing() {
+        let tracker = CostTracker::default();
+        assert_eq!(tracker.record("mystery", &usage(100, 100)), 0.0);
+        assert_eq!(tracker.today().requests, 1);
+    }
+
+    #[test]
+    fn test_budget_exhaustion() {
+        let tracker = CostTracker::new(CostTracker::default_prices(), Some(1.0));
+        assert!(!tracker.is_exhausted());
+        tracker.record("deepseek-chat", &usage(0, 1_000_000));
+        assert!(tracker.is_exhausted());
+        assert_eq!(tracker.remaining_budget_usd(), Some(0.0));
+    }
+}
@@ -0,0 +1,386 @@
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::task::JoinSet;
+use tracing::{debug, info};
+
+use hydra_core::signal::MintSignal;
+use hydra_core::traits::{AiAnalyzer, ScoredSignal};
+
+type BoxFuture<'a, T> = Pin<Box<dyn Future<Output = T> + Send + 'a>>;
+
+/// Object-safe adapter so members of different types can share one ensemble.
+trait DynAnalyzer: Send + Sync {
+    fn analyze_boxed<'a>(&'a self, signal: &'a MintSignal) -> BoxFuture<'a, Option<ScoredSignal>>;
+}
+
+impl<A: AiAnalyzer> DynAnalyzer for A {
+    fn analyze_boxed<'a>(&'a self, signal: &'a MintSignal) -> BoxFuture<'a, Option<ScoredSignal>> {
+        Box::pin(self.analyze(signal))
+    }
+}
+
+/// How member votes are combined into one decision
+#[derive(Debug, Clone)]
+pub enum EnsemblePolicy {
+    /// Weighted mean of scores; buy when the mean reaches `buy_threshold`
+    WeightedMean { buy_threshold: f64 },
+    /// Buy when a strict majority of responders votes `should_buy`
+    MajorityVote,
+    /// Buy only when every responder votes buy with at least `min_confidence`
+    MinConfidenceVeto { min_confidence: f64 },
+    /// Buy as soon as `k` members vote buy, without waiting for the rest
+    FirstKOfN { k: usize },
+}
+
+#[derive(Default)]
+struct MemberStats {
+    responses: AtomicU64,
+    late: AtomicU64,
+    failures: AtomicU64,
+    agreements: AtomicU64,
+    total_latency_us: AtomicU64,
+}
+
+struct Member {
+    name: String,
+    weight: f64,
+    analyzer: Arc<dyn DynAnalyzer>,
+    stats: MemberStats,
+}
+
+/// Snapshot of how a single member has performed inside the ensemble
+#[derive(Debug, Clone)]
+pub struct MemberReport {
+    pub name: String,
+    pub weight: f64,
+    pub responses: u64,
+    pub late: u64,
+    pub failures: u64,
+    /// Share of responses whose `should_buy` matched the final decision
+    pub agreement_rate: f64,
+    pub mean_latency_ms: f64,
+}
+
+#[derive(Debug, Clone)]
+struct Vote {
+    member: usize,
+    score: f64,
+    should_buy: bool,
+}
+
+/// Queries several analyzers concurrently under one shared deadline and
+/// combines their votes according to an `EnsemblePolicy`.
+pub struct EnsembleAnalyzer {
+    members: Vec<Member>,
+    policy: EnsemblePolicy,
+    deadline: Duration,
+}
+
+impl EnsembleAnalyzer {
+    pub fn new(deadline: Duration, policy: EnsemblePolicy) -> Self {
+        Self {
+            members: Vec::new(),
+            policy,
+            deadline,
+        }
+    }
+
+    pub fn with_member<A>(mut self, name: impl Into<String>, weight: f64, analyzer: A) -> Self
+    where
+        A: AiAnalyzer + 'static,
+    {
+        self.members.push(Member {
+            name: name.into(),
+            weight: weight.max(0.0),
+            analyzer: Arc::new(analyzer),
+            stats: MemberStats::default(),
+        });
+        self
+    }
+
+    pub fn member_reports(&self) -> Vec<MemberReport> {
+        self.members
+            .iter()
+            .map(|m| {
+                let responses = m.stats.responses.load(Ordering::Relaxed);
+                let agreements = m.stats.agreements.load(Ordering::Relaxed);
+                let latency_us = m.stats.total_latency_us.load(Ordering::Relaxed);
+                let (agreement_rate, mean_latency_ms) = if responses > 0 {
+                    (
+                        agreements as f64 / responses as f64,
+                        latency_us as f64 / responses as f64 / 1_000.0,
+                    )
+                } else {
+                    (0.0, 0.0)
+                };
+                MemberReport {
+                    name: m.name.clone(),
+                    weight: m.weight,
+                    responses,
+                    late: m.stats.late.load(Ordering::Relaxed),
+                    failures: m.stats.failures.load(Ordering::Relaxed),
+                    agreement_rate,
+                    mean_latency_ms,
+                }
+            })
+            .collect()
+    }
+
+    async fn collect_votes(&self, signal: &MintSignal) -> Vec<Vote> {
+        let deadline = tokio::time::Instant::now() + self.deadline;
+        let mut set = JoinSet::new();
+        for (idx, member) in self.members.iter().enumerate() {
+            let analyzer = Arc::clone(&member.analyzer);
+            let signal = signal.clone();
+            set.spawn(async move {
+                let started = Instant::now();
+                let result = analyzer.analyze_boxed(&signal).await;
+                (idx, result, started.elapsed())
+            });
+        }
+
+        let mut votes = Vec::with_capacity(self.members.len());
+        let mut answered = vec![false; self.members.len()];
+        loop {
+            match tokio::time::timeout_at(deadline, set.join_next()).await {
+                Ok(Some(Ok((idx, result, latency)))) => {
+                    answered[idx] = true;
+                    let stats = &self.members[idx].stats;
+                    match result {
+                        Some(scored) => {
+                            stats.responses.fetch_add(1, Ordering::Relaxed);
+                            stats
+                                .total_latency_us
+                                .fetch_add(latency.as_micros() as u64, Ordering::Relaxed);
+                            votes.push(Vote {
+                                member: idx,
+                                score: scored.score.clamp(0.0, 1.0),
+                                should_buy: scored.should_buy,
+                            });
+                        }
+                        None => {
+                            stats.failures.fetch_add(1, Ordering::Relaxed);
+                        }
+                    }
+                    if self.decided_early(&votes) {
+                        break;
+                    }
+                }
+                Ok(Some(Err(e))) => {
+                    debug!(error = %e, "Ensemble member task failed");
+                }
+                Ok(None) => break,
+                Err(_) => {
+                    debug!("Ensemble deadline reached");
+                    break;
+                }
+            }
+        }
+
+        // Dropping the JoinSet aborts members that have not answered yet.
+        for (idx, done) in answered.iter().enumerate() {
+            if !done {
+                self.members[idx].stats.late.fetch_add(1, Ordering::Relaxed);
+            }
+        }
+        votes
+    }
+
+    fn decided_early(&self, votes: &[Vote]) -> bool {
+        match self.policy {
+            EnsemblePolicy::FirstKOfN { k } => votes.iter().filter(|v| v.should_buy).count() >= k,
+            _ => false,
+        }
+    }
+
+    fn combine(&self, votes: &[Vote]) -> Option<(f64, bool)> {
+        combine_votes(&self.policy, &self.members, votes)
+    }
+}
+
+fn combine_votes(
+    policy: &EnsemblePolicy,
+    members: &[Member],
+    votes: &[Vote],
+) -> Option<(f64, bool)> {
+    if votes.is_empty() {
+        return None;
+    }
+    let mean = votes.iter().map(|v| v.score).sum::<f64>() / votes.len() as f64;
+    let buys = votes.iter().filter(|v| v.should_buy).count();
+    let result = match *policy {
+        EnsemblePolicy::WeightedMean { buy_threshold } => {
+            let total_weight: f64 = votes.iter().map(|v| members[v.member].weight).sum();
+            let score = if total_weight > 0.0 {
+                votes
+                    .iter()
+                    .map(|v| v.score * members[v.member].weight)
+                    .sum::<f64>()
+                    / total_weight
+            } else {
+                mean
+            };
+            (score, score >= buy_threshold)
+        }
+        EnsemblePolicy::MajorityVote => (mean, buys * 2 > votes.len()),
+        EnsemblePolicy::MinConfidenceVeto { min_confidence } => {
+            let min = votes.iter().map(|v| v.score).fold(f64::INFINITY, f64::min);
+            (min, buys == votes.len() && min >= min_confidence)
+        }
+        EnsemblePolicy::FirstKOfN { k } => (mean, buys >= k),
+    };
+    Some(result)
+}
+
+impl AiAnalyzer for EnsembleAnalyzer {
+    async fn analyze(&self, signal: &MintSignal) -> Option<ScoredSignal> {
+        let votes = self.collect_votes(signal).await;
+        let (score, should_buy) = self.combine(&votes)?;
+
+        for vote in &votes {
+            if vote.should_buy == should_buy {
+                self.members[vote.member]
+                    .stats
+                    .agreements
+                    .fetch_add(1, Ordering::Relaxed);
+            }
+        }
+
+        info!(
+            mint = %signal.mint_address,
+            responders = votes.len(),
+            members = self.members.len(),
+            score,
+            should_buy,
+            "Ensemble scoring complete"
+        );
+
+        Some(ScoredSignal {
+            signal: signal.clone(),
+            score,
+            should_buy,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct FixedAnalyzer {
+        score: f64,
+        delay: Duration,
+    }
+
+    impl AiAnalyzer for FixedAnalyzer {
+        async fn analyze(&self, signal: &MintSignal) -> Option<ScoredSignal> {
+            tokio::time::sleep(self.delay).await;
+            Some(ScoredSignal {
+                signal: signal.clone(),
+                score: self.score,
+                should_buy: self.score > 0.6,
+            })
+        }
+    }
+
+    fn fixed(score: f64, delay_ms: u64) -> FixedAnalyzer {
+        FixedAnalyzer {
+            score,
+            delay: Duration::from_millis(delay_ms),
+        }
+    }
+
+    fn signal() -> MintSignal {
+        MintSignal::new(
+            "test_mint".to_string(),
+            1000.0,
+            500.0,
+            0.001,
+            100,
+            800.0,
+            10.0,
+        )
+    }
+
+    #[tokio::test]
+    async fn test_weighted_mean() {
+        // (0.9 * 3 + 0.3 * 1) / 4 = 0.75
+        let ensemble = EnsembleAnalyzer::new(
+            Duration::from_millis(500),
+            EnsemblePolicy::WeightedMean { buy_threshold: 0.7 },
+        )
+        .with_member("a", 3.0, fixed(0.9, 0))
+        .with_member("b", 1.0, fixed(0.3, 0));
+        let scored = ensemble.analyze(&signal()).await.unwrap();
+        assert!((scored.score - 0.75).abs() < 1e-10);
+        assert!(scored.should_buy);
+    }
+
+    #[tokio::test]
+    async fn test_majority_vote() {
+        let ensemble =
+            EnsembleAnalyzer::new(Duration::from_millis(500), EnsemblePolicy::MajorityVote)
+                .with_member("a", 1.0, fixed(0.9, 0))
+                .with_member("b", 1.0, fixed(0.2, 0))
+                .with_member("c", 1.0, fixed(0.3, 0));
+        let scored = ensemble.analyze(&signal()).await.unwrap();
+        assert!(!scored.should_buy);
+    }
+
+    #[tokio::test]
+    async fn test_min_confidence_veto() {
+        let ensemble = EnsembleAnalyzer::new(
+            Duration::from_millis(500),
+            EnsemblePolicy::MinConfidenceVeto {
+                min_confidence: 0.8,
+            },
+        )
+        .with_member("a", 1.0, fixed(0.95, 0))
+        .with_member("b", 1.0, fixed(0.7, 0));
+        let scored = ensemble.analyze(&signal()).await.unwrap();
+        assert!((scored.score - 0.7).abs() < 1e-10);
+        assert!(!scored.should_buy);
+    }
+
+    #[tokio::test]
+    async fn test_late_member_ignored() {
+        let ensemble =
+            EnsembleAnalyzer::new(Duration::from_millis(50), EnsemblePolicy::MajorityVote)
+                .with_member("fast", 1.0, fixed(0.9, 0))
+                .with_member("slow", 1.0, fixed(0.1, 1_000));
+        let scored = ensemble.analyze(&signal()).await.unwrap();
+        assert!(scored.should_buy);
+
+        let reports = ensemble.member_reports();
+        assert_eq!(reports[0].responses, 1);
+        assert!((reports[0].agreement_rate - 1.0).abs() < 1e-10);
+        assert_eq!(reports[1].responses, 0);
+        assert_eq!(reports[1].late, 1);
+    }
+
+    #[tokio::test]
+    async fn test_first_k_of_n_decides_early() {
+        let ensemble = EnsembleAnalyzer::new(
+            Duration::from_millis(2_000),
+            EnsemblePolicy::FirstKOfN { k: 2 },
+        )
+        .with_member("a", 1.0, fixed(0.9, 0))
+        .with_member("b", 1.0, fixed(0.8, 10))
+        .with_member("c", 1.0, fixed(0.1, 1_000));
+        let started = Instant::now();
+        let scored = ensemble.analyze(&signal()).await.unwrap();
+        assert!(scored.should_buy);
+        assert!(started.elapsed() < Duration::from_millis(500));
+    }
+
+    #[tokio::test]
+    async fn test_no_responders_returns_none() {
+        let ensemble =
+            EnsembleAnalyzer::new(Duration::from_millis(10), EnsemblePolicy::MajorityVote)
+                .with_member("slow", 1.0, fixed(0.9, 1_000));
+        assert!(ensemble.analyze(&signal()).await.is_none());
+    }
+}
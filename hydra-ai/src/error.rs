@@ -0,0 +1,16 @@
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum AiError {
+    #[error("Daily AI budget exhausted")]
+    BudgetExhausted,
+
+    #[error("AI request rate limited (upstream: {upstream})")]
+    RateLimited { upstream: bool },
+
+    #[error("DeepSeek API error: HTTP {status}")]
+    Api { status: u16 },
+
+    #[error("No recorded response for request {key}")]
+    ReplayMiss { key: String },
+}
@@ -0,0 +1,181 @@
+use std::collections::VecDeque;
+use std::sync::Mutex;
+use std::time::Duration;
+
+/// An OpenAI-compatible chat completions endpoint
+#[derive(Debug, Clone)]
+pub struct Endpoint {
+    pub api_url: String,
+    pub api_key: String,
+}
+
+impl Endpoint {
+    pub fn new(api_url: String, api_key: String) -> Self {
+        Self { api_url, api_key }
+    }
+}
+
+/// When to fire the hedged request at the secondary endpoint.
+/// The delay is the `quantile` of recent primary latencies, clamped to
+/// `[min_delay, max_delay]`. Until `min_samples` latencies have been seen
+/// `max_delay` is used.
+#[derive(Debug, Clone)]
+pub struct HedgePolicy {
+    pub quantile: f64,
+    pub min_delay: Duration,
+    pub max_delay: Duration,
+    pub window: usize,
+    pub min_samples: usize,
+}
+
+impl Default for HedgePolicy {
+    fn default() -> Self {
+        Self {
+            quantile: 0.9,
+            min_delay: Duration::from_millis(100),
+            max_delay: Duration::from_millis(400),
+            window: 256,
+            min_samples: 20,
+        }
+    }
+}
+
+impl HedgePolicy {
+    pub fn with_quantile(mut self, quantile: f64) -> Self {
+        self.quantile = quantile.clamp(0.0, 1.0);
+        self
+    }
+}
+
+/// Ring buffer of recent primary endpoint latencies
+pub struct LatencyWindow {
+    samples: Mutex<VecDeque<Duration>>,
+    capacity: usize,
+}
+
+impl LatencyWindow {
+    pub fn new(capacity: usize) -> Self {
+        let capacity = capacity.max(1);
+        Self {
+            samples: Mutex::new(VecDeque::with_capacity(capacity)),
+            capacity,
+        }
+    }
+
+    pub fn record(&self, latency: Duration) {
+        let mut samples = self.samples.lock().unwrap_or_else(|e| e.into_inner());
+        if samples.len() == self.capacity {
+            samples.pop_front();
+        }
+        samples.push_back(latency);
+    }
+
+    pub fn len(&self) -> usize {
+        self.samples.lock().unwrap_or_else(|e| e.into_inner()).len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Nearest-rank quantile, `None` when no samples have been recorded
+    pub fn quantile(&self, q: f64) -> Option<Duration> {
+        let mut sorted: Vec<Duration> = self
+            .samples
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .iter()
+            .copied()
+            .collect();
+        if sorted.is_empty() {
+            return None;
+        }
+        sorted.sort_unstable();
+        let rank = (q.clamp(0.0, 1.0) * sorted.len() as f64).ceil() as usize;
+        Some(sorted[rank.saturating_sub(1).min(sorted.len() - 1)])
+    }
+}
+
+/// Secondary endpoint plus the state needed to decide when to hedge
+pub(crate) struct Hedge {
+    pub(crate) endpoint: Endpoint,
+    pub(crate) policy: HedgePolicy,
+    pub(crate) latencies: LatencyWindow,
+}
+
+impl Hedge {
+    pub(crate) fn new(endpoint: Endpoint, policy: HedgePolicy) -> Self {
+        let latencies = LatencyWindow::new(policy.window);
+        Self {
+            endpoint,
+            policy,
+            latencies,
+        }
+    }
+
+    pub(crate) fn delay(&self) -> Duration {
+        if self.latencies.len() < self.policy.min_samples {
+            return self.policy.max_delay;
+        }
+        self.latencies
+            .quantile(self.policy.quantile)
+            .unwrap_or(self.policy.max_delay)
+            // Not clamp, which panics on an inverted policy
+            .max(self.policy.min_delay)
+            .min(self.policy.max_delay)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ms(v: u64) -> Duration {
+        Duration::from_millis(v)
+    }
+
+    #[test]
+    fn test_window_quantile_and_eviction() {
+        let window = LatencyWindow::new(10);
+        assert_eq!(window.quantile(0.9), None);
+        for v in 1..=10 {
+            window.record(ms(v * 10));
+        }
+        assert_eq!(window.quantile(0.9), Some(ms(90)));
+        assert_eq!(window.quantile(1.0), Some(ms(100)));
+        window.record(ms(500));
+        assert_eq!(window.len(), 10);
+        assert_eq!(window.quantile(0.0), Some(ms(20)));
+    }
+
+    #[test]
+    fn test_delay_is_clamped_and_warms_up() {
+        let policy = HedgePolicy {
+            min_samples: 5,
+            ..HedgePolicy::default()
+        };
+        let hedge = Hedge::new(Endpoint::new(String::new(), String::new()), policy);
+        assert_eq!(hedge.delay(), ms(400));
+        for _ in 0..5 {
+            hedge.latencies.record(ms(10));
+        }
+        assert_eq!(hedge.delay(), ms(100));
+        for _ in 0..50 {
+            hedge.latencies.record(ms(900));
+        }
+        assert_eq!(hedge.delay(), ms(400));
+    }
+
+    #[test]
+    fn test_inverted_delay_bounds_do_not_panic() {
+        let policy = HedgePolicy {
+            min_delay: ms(500),
+            max_delay: ms(100),
+            min_samples: 1,
+            ..HedgePolicy::default()
+        };
+        let hedge = Hedge::new(Endpoint::new(String::new(), String::new()), policy);
+        hedge.latencies.record(ms(10));
+        assert_eq!(hedge.delay(), ms(100));
+    }
+}
@@ -0,0 +1,123 @@
+use hydra_core::signal::MintSignal;
+use hydra_core::traits::{AiAnalyzer, ScoredSignal};
+
+use crate::scorer::{AiScore, Scorer};
+
+/// Rule-based scorer that needs no network access.
+/// Confidence is the fraction of checks the signal passes.
+#[derive(Debug, Clone)]
+pub struct HeuristicScorer {
+    pub min_liquidity_usd: f64,
+    pub max_top_holder_pct: f64,
+    pub min_holder_count: u64,
+    pub min_volume_to_mcap: f64,
+    pub buy_threshold: f64,
+}
+
+impl HeuristicScorer {
+    pub fn new(
+        min_liquidity_usd: f64,
+        max_top_holder_pct: f64,
+        min_holder_count: u64,
+        min_volume_to_mcap: f64,
+        buy_threshold: f64,
+    ) -> Self {
+        Self {
+            min_liquidity_usd,
+            max_top_holder_pct,
+            min_holder_count,
+            min_volume_to_mcap,
+            buy_threshold,
+        }
+    }
+
+    pub fn score(&self, signal: &MintSignal) -> AiScore {
+        let volume_to_mcap = if signal.market_cap_usd > 0.0 {
+            signal.volume_24h_usd / signal.market_cap_usd
+        } else {
+            0.0
+        };
+        let checks = [
+            (signal.liquidity_usd >= self.min_liquidity_usd, "liquidity"),
+            (
+                signal.top_holder_pct <= self.max_top_holder_pct,
+                "top_holder",
+            ),
+            (signal.holder_count >= self.min_holder_count, "holders"),
+            (volume_to_mcap >= self.min_volume_to_mcap, "volume"),
+        ];
+        let passed = checks.iter().filter(|(ok, _)| *ok).count();
+        let failed: Vec<&str> = checks
+            .iter()
+            .filter(|(ok, _)| !*ok)
+            .map(|(_, name)| *name)
+            .collect();
+        let confidence = passed as f64 / checks.len() as f64;
+        let reasoning = if failed.is_empty() {
+            "heuristic: all checks passed".to_string()
+        } else {
+            format!("heuristic: failed {}", failed.join(", "))
+        };
+        AiScore {
+            confidence,
+            reasoning,
+            should_buy: confidence >= self.buy_threshold,
+        }
+    }
+}
+
+impl Default for HeuristicScorer {
+    fn default() -> Self {
+        Self::new(5_000.0, 30.0, 50, 0.5, 0.75)
+    }
+}
+
+impl Scorer for HeuristicScorer {
+    async fn score(&self, signal: &MintSignal) -> anyhow::Result<AiScore> {
+        Ok(HeuristicScorer::score(self, signal))
+    }
+}
+
+impl AiAnalyzer for HeuristicScorer {
+    async fn analyze(&self, signal: &MintSignal) -> Option<ScoredSignal> {
+        let score = self.score(signal);
+        Some(ScoredSignal {
+            signal: signal.clone(),
+            score: score.confidence,
+            should_buy: score.should_buy,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_heuristic_all_checks_pass() {
+        let h = HeuristicScorer::default();
+        let s = MintSignal::new(
+            "m".to_string(),
+            10_000.0,
+            8_000.0,
+            0.001,
+            200,
+            6_000.0,
+            10.0,
+        );
+        let score = h.score(&s);
+        assert!((score.confidence - 1.0).abs() < 1e-10);
+        assert!(score.should_buy);
+    }
+
+    #[test]
+    fn test_heuristic_partial_checks() {
+        let h = HeuristicScorer::default();
+        // fails liquidity and top holder → 2/4
+        let s = MintSignal::new("m".to_string(), 10_000.0, 8_000.0, 0.001, 200, 100.0, 80.0);
+        let score = h.score(&s);
+        assert!((score.confidence - 0.5).abs() < 1e-10);
+        assert!(!score.should_buy);
+        assert!(score.reasoning.contains("liquidity"));
+    }
+}
@@ -0,0 +1,111 @@
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+use tokio::io::AsyncWriteExt;
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+use tracing::warn;
+
+/// Lines queued before new ones are dropped
+pub(crate) const QUEUE_CAPACITY: usize = 4_096;
+
+/// Appends values as JSON lines from a background task. The queue is
+/// bounded and `send` never waits: when the disk falls behind, lines are
+/// dropped with a warning instead of stalling the caller or growing
+/// memory.
+pub(crate) struct JsonlWriter<T> {
+    name: &'static str,
+    tx: mpsc::Sender<T>,
+}
+
+impl<T> Clone for JsonlWriter<T> {
+    fn clone(&self) -> Self {
+        Self {
+            name: self.name,
+            tx: self.tx.clone(),
+        }
+    }
+}
+
+impl<T: Serialize + Send + 'static> JsonlWriter<T> {
+    /// Start the writer task for `path`. The task ends once every handle
+    /// has been dropped and the queue is drained.
+    pub(crate) fn spawn(
+        name: &'static str,
+        path: impl Into<PathBuf>,
+        capacity: usize,
+    ) -> (Self, JoinHandle<()>) {
+        let path = path.into();
+        let (tx, mut rx) = mpsc::channel::<T>(capacity.max(1));
+        let handle = tokio::spawn(async move {
+            let mut file = match open_append(&path).await {
+                Ok(file) => file,
+                Err(e) => {
+                    warn!(log = name, path = %path.display(), error = %e, "Failed to open JSONL log");
+                    return;
+                }
+            };
+            while let Some(value) = rx.recv().await {
+                let mut line = match serde_json::to_vec(&value) {
+                    Ok(line) => line,
+                    Err(e) => {
+                        warn!(log = name, error = %e, "Failed to serialize JSONL entry");
+                        continue;
+                    }
+                };
+                line.push(b'\n');
+                if let Err(e) = file.write_all(&line).await {
+                    warn!(log = name, error = %e, "Failed to write JSONL entry");
+                }
+            }
+            if let Err(e) = file.flush().await {
+                warn!(log = name, error = %e, "Failed to flush JSONL log");
+            }
+        });
+        (Self { name, tx }, handle)
+    }
+
+    pub(crate) fn send(&self, value: T) {
+        match self.tx.try_send(value) {
+            Ok(()) => {}
+            Err(mpsc::error::TrySendError::Full(_)) => {
+                warn!(log = self.name, "JSONL queue full, entry dropped");
+            }
+            Err(mpsc::error::TrySendError::Closed(_)) => {
+                warn!(log = self.name, "JSONL writer has stopped, entry dropped");
+            }
+        }
+    }
+}
+
+async fn open_append(path: &Path) -> std::io::Result<tokio::fs::File> {
+    if let Some(parent) = path.parent() {
+        tokio::fs::create_dir_all(parent).await?;
+    }
+    tokio::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test(flavor = "current_thread")]
+    async fn test_full_queue_drops_instead_of_blocking() {
+        let path = std::env::temp_dir().join(format!("hydra_jsonl_{}.jsonl", std::process::id()));
+        std::fs::remove_file(&path).ok();
+        // The writer task cannot run until we yield, so the queue fills
+        let (writer, handle) = JsonlWriter::spawn("test", &path, 2);
+        for i in 0..5u32 {
+            writer.send(i);
+        }
+        drop(writer);
+        handle.await.unwrap();
+
+        let lines = std::fs::read_to_string(&path).unwrap();
+        assert_eq!(lines, "0\n1\n");
+        std::fs::remove_file(&path).ok();
+    }
+}
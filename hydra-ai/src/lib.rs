@@ -1,5 +1,35 @@
+pub mod audit;
 pub mod cache;
+pub mod calibration;
+pub mod coalesce;
+pub mod cost;
+pub mod ensemble;
+pub mod error;
+pub mod hedge;
+pub mod heuristic;
+mod jsonl;
+pub mod limiter;
+pub mod metrics;
+pub mod replay;
 pub mod scorer;
 
-pub use cache::ScoreCache;
-pub use scorer::{AiScore, DeepSeekScorer};
+pub use audit::{
+    join_outcomes, load_audit_log, AuditLog, AuditRecord, AuditReport, ConfidenceBucket,
+    JoinedOutcome,
+};
+pub use cache::{CacheStats, CachedScore, ScoreCache, SignalSnapshot};
+pub use calibration::{
+    samples_from_outcomes, CalibratedScorer, Calibrator, ReliabilityBin, ReliabilityReport,
+};
+pub use coalesce::{CoalescingScorer, RescoreTrigger, SingleFlight};
+pub use cost::{CostSnapshot, CostTracker, ModelPrice, Usage};
+pub use ensemble::{EnsembleAnalyzer, EnsemblePolicy, MemberReport};
+pub use error::AiError;
+pub use hedge::{Endpoint, HedgePolicy, LatencyWindow};
+pub use heuristic::HeuristicScorer;
+pub use limiter::{RateLimiter, TokenBucket};
+pub use metrics::AiMetrics;
+pub use replay::{
+    request_key, MissPolicy, RecordedResponse, ReplayScorer, ReplayStore, ResponseRecorder,
+};
+pub use scorer::{AiScore, DeepSeekScorer, Scorer, PROMPT_VERSION};
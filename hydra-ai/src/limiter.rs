@@ -0,0 +1,200 @@
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Classic token bucket. The balance may go negative when actual usage
+/// turns out higher than what was reserved; the debt is repaid by refill.
+pub struct TokenBucket {
+    capacity: f64,
+    refill_per_sec: f64,
+    state: Mutex<BucketState>,
+}
+
+struct BucketState {
+    tokens: f64,
+    last_refill: Instant,
+}
+
+impl TokenBucket {
+    pub fn new(capacity: f64, refill_per_sec: f64) -> Self {
+        Self {
+            capacity,
+            refill_per_sec,
+            state: Mutex::new(BucketState {
+                tokens: capacity,
+                last_refill: Instant::now(),
+            }),
+        }
+    }
+
+    /// Bucket that holds one minute worth of budget and refills continuously
+    pub fn per_minute(limit: u32) -> Self {
+        Self::new(limit as f64, limit as f64 / 60.0)
+    }
+
+    /// How long until `amount` could be taken, or `None` if it can be taken now
+    pub fn wait_time(&self, amount: f64) -> Option<Duration> {
+        let mut state = self.lock();
+        self.refill(&mut state);
+        self.deficit_wait(&state, amount)
+    }
+
+    /// Take `amount` if available right now.
+    pub fn try_take(&self, amount: f64) -> bool {
+        let mut state = self.lock();
+        self.refill(&mut state);
+        if state.tokens >= amount {
+            state.tokens -= amount;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Correct an earlier reservation: positive `delta` charges more,
+    /// negative refunds (never above capacity).
+    pub fn adjust(&self, delta: f64) {
+        let mut state = self.lock();
+        self.refill(&mut state);
+        state.tokens = (state.tokens - delta).min(self.capacity);
+    }
+
+    pub fn available(&self) -> f64 {
+        let mut state = self.lock();
+        self.refill(&mut state);
+        state.tokens
+    }
+
+    fn refill(&self, state: &mut BucketState) {
+        let now = Instant::now();
+        let elapsed = now.duration_since(state.last_refill).as_secs_f64();
+        state.tokens = (state.tokens + elapsed * self.refill_per_sec).min(self.capacity);
+        state.last_refill = now;
+    }
+
+    fn deficit_wait(&self, state: &BucketState, amount: f64) -> Option<Duration> {
+        if state.tokens >= amount {
+            return None;
+        }
+        if self.refill_per_sec <= 0.0 {
+            return Some(Duration::MAX);
+        }
+        Some(Duration::from_secs_f64(
+            (amount - state.tokens) / self.refill_per_sec,
+        ))
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, BucketState> {
+        // A poisoned bucket only holds plain numbers, so keep using it.
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+/// Limits LLM calls by requests per minute and tokens per minute.
+pub struct RateLimiter {
+    requests: TokenBucket,
+    tokens: TokenBucket,
+    max_wait: Duration,
+}
+
+impl RateLimiter {
+    pub fn new(requests_per_minute: u32, tokens_per_minute: u32, max_wait: Duration) -> Self {
+        Self {
+            requests: TokenBucket::per_minute(requests_per_minute),
+            tokens: TokenBucket::per_minute(tokens_per_minute),
+            max_wait,
+        }
+    }
+
+    /// Reserve one request and `estimated_tokens`, waiting at most
+    /// `max_wait` for budget to refill. Returns `false` if the call
+    /// should not be made.
+    pub async fn acquire(&self, estimated_tokens: u32) -> bool {
+        let deadline = Instant::now() + self.max_wait;
+        loop {
+            let wait = self
+                .requests
+                .wait_time(1.0)
+                .max(self.tokens.wait_time(estimated_tokens as f64));
+            match wait {
+                None => {
+                    if self.requests.try_take(1.0) {
+                        if self.tokens.try_take(estimated_tokens as f64) {
+                            return true;
+                        }
+                        self.requests.adjust(-1.0);
+                    }
+                }
+                Some(wait) => {
+                    // A zero refill rate waits forever, which no deadline allows
+                    if Instant::now()
+                        .checked_add(wait)
+                        .is_none_or(|ready| ready > deadline)
+                    {
+                        return false;
+                    }
+                    tokio::time::sleep(wait).await;
+                }
+            }
+        }
+    }
+
+    /// Reconcile the token reservation with what the API reported.
+    pub fn settle(&self, estimated_tokens: u32, actual_tokens: u32) {
+        self.tokens
+            .adjust(actual_tokens as f64 - estimated_tokens as f64);
+    }
+}
+
+/// Rough token estimate for a prompt (~4 characters per token) plus
+/// room for the short JSON answer we ask for.
+pub fn estimate_tokens(prompt: &str, max_completion_tokens: u32) -> u32 {
+    (prompt.len() as u32).div_ceil(4) + max_completion_tokens
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bucket_take_and_refuse() {
+        let bucket = TokenBucket::new(2.0, 0.0);
+        assert!(bucket.try_take(1.0));
+        assert!(bucket.try_take(1.0));
+        assert!(!bucket.try_take(1.0));
+        assert_eq!(bucket.wait_time(1.0), Some(Duration::MAX));
+    }
+
+    #[test]
+    fn test_bucket_adjust_refund_capped() {
+        let bucket = TokenBucket::new(10.0, 0.0);
+        assert!(bucket.try_take(4.0));
+        bucket.adjust(2.0);
+        assert!((bucket.available() - 4.0).abs() < 1e-9);
+        bucket.adjust(-100.0);
+        assert!((bucket.available() - 10.0).abs() < 1e-9);
+    }
+
+    #[tokio::test]
+    async fn test_limiter_denies_when_wait_too_long() {
+        // 1 request per minute, no meaningful wait allowed
+        let limiter = RateLimiter::new(1, 10_000, Duration::from_millis(10));
+        assert!(limiter.acquire(100).await);
+        assert!(!limiter.acquire(100).await);
+    }
+
+    #[tokio::test]
+    async fn test_limiter_zero_limit_denies_without_panic() {
+        let limiter = RateLimiter::new(0, 10_000, Duration::from_secs(60));
+        assert!(!limiter.acquire(100).await);
+    }
+
+    #[tokio::test]
+    async fn test_limiter_token_budget() {
+        let limiter = RateLimiter::new(100, 500, Duration::ZERO);
+        assert!(limiter.acquire(400).await);
+        assert!(!limiter.acquire(400).await);
+        // the first call used far fewer tokens than reserved
+        limiter.settle(400, 50);
+        assert!(limiter.acquire(400).await);
+    }
+}
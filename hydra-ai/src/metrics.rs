@@ -0,0 +1,116 @@
+use prometheus_client::{
+    metrics::{counter::Counter, gauge::Gauge},
+    registry::Registry,
+};
+use std::sync::atomic::AtomicU64;
+
+use crate::cost::{CostSnapshot, Usage};
+
+#[derive(Clone)]
+pub struct AiMetrics {
+    pub requests: Counter,
+    pub failures: Counter,
+    pub rate_limited_local: Counter,
+    pub rate_limited_upstream: Counter,
+    pub fallback_used: Counter,
+    pub hedges_fired: Counter,
+    pub hedges_won: Counter,
+    pub prompt_tokens: Counter,
+    pub completion_tokens: Counter,
+    pub cost_usd_total: Counter<f64, AtomicU64>,
+    pub daily_cost_usd: Gauge<f64, AtomicU64>,
+}
+
+impl AiMetrics {
+    pub fn new(registry: &mut Registry) -> Self {
+        let requests: Counter = Counter::default();
+        let failures: Counter = Counter::default();
+        let rate_limited_local: Counter = Counter::default();
+        let rate_limited_upstream: Counter = Counter::default();
+        let fallback_used: Counter = Counter::default();
+        let hedges_fired: Counter = Counter::default();
+        let hedges_won: Counter = Counter::default();
+        let prompt_tokens: Counter = Counter::default();
+        let completion_tokens: Counter = Counter::default();
+        let cost_usd_total: Counter<f64, AtomicU64> = Counter::default();
+        let daily_cost_usd: Gauge<f64, AtomicU64> = Gauge::default();
+
+        registry.register(
+            "hydra_ai_requests",
+            "LLM scoring requests sent",
+            requests.clone(),
+        );
+        registry.register(
+            "hydra_ai_failures",
+            "LLM scoring requests that failed",
+            failures.clone(),
+        );
+        registry.register(
+            "hydra_ai_rate_limited_local",
+            "LLM requests held back by the local rate limiter",
+            rate_limited_local.clone(),
+        );
+        registry.register(
+            "hydra_ai_rate_limited_upstream",
+            "LLM requests rejected upstream with HTTP 429",
+            rate_limited_upstream.clone(),
+        );
+        registry.register(
+            "hydra_ai_fallback_used",
+            "Signals scored by the fallback scorer instead of the LLM",
+            fallback_used.clone(),
+        );
+        registry.register(
+            "hydra_ai_hedges_fired",
+            "Hedged requests sent to the secondary endpoint",
+            hedges_fired.clone(),
+        );
+        registry.register(
+            "hydra_ai_hedges_won",
+            "Hedged requests answered before the primary endpoint",
+            hedges_won.clone(),
+        );
+        registry.register(
+            "hydra_ai_prompt_tokens",
+            "Prompt tokens consumed",
+            prompt_tokens.clone(),
+        );
+        registry.register(
+            "hydra_ai_completion_tokens",
+            "Completion tokens consumed",
+            completion_tokens.clone(),
+        );
+        registry.register(
+            "hydra_ai_cost_usd",
+            "Cumulative LLM spend in USD",
+            cost_usd_total.clone(),
+        );
+        registry.register(
+            "hydra_ai_daily_cost_usd",
+            "LLM spend in USD for the current UTC day",
+            daily_cost_usd.clone(),
+        );
+
+        Self {
+            requests,
+            failures,
+            rate_limited_local,
+            rate_limited_upstream,
+            fallback_used,
+            hedges_fired,
+            hedges_won,
+            prompt_tokens,
+            completion_tokens,
+            cost_usd_total,
+            daily_cost_usd,
+        }
+    }
+
+    pub fn observe_usage(&self, usage: &Usage, cost_usd: f64, today: &CostSnapshot) {
+        self.prompt_tokens.inc_by(usage.prompt_tokens as u64);
+        self.completion_tokens
+            .inc_by(usage.completion_tokens as u64);
+        self.cost_usd_total.inc_by(cost_usd);
+        self.daily_cost_usd.set(today.cost_usd);
+    }
+}
@@ -0,0 +1,309 @@
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::io::BufRead;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use tokio::task::JoinHandle;
+use tracing::{debug, info, warn};
+
+use hydra_core::signal::MintSignal;
+use hydra_core::traits::{AiAnalyzer, ScoredSignal};
+
+use crate::cost::Usage;
+use crate::error::AiError;
+use crate::heuristic::HeuristicScorer;
+use crate::jsonl::{JsonlWriter, QUEUE_CAPACITY};
+use crate::scorer::{build_prompt, score_from_content, AiScore, Scorer};
+
+const FNV_OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+/// Stable key for an LLM request. Uses FNV-1a rather than `DefaultHasher`
+/// so keys stay identical across processes and Rust versions.
+pub fn request_key(model: &str, prompt: &str) -> String {
+    let mut hash = FNV_OFFSET;
+    for byte in model.bytes().chain([0u8]).chain(prompt.bytes()) {
+        hash ^= byte as u64;
+        hash = hash.wrapping_mul(FNV_PRIME);
+    }
+    format!("{hash:016x}")
+}
+
+/// One recorded LLM answer
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecordedResponse {
+    pub key: String,
+    pub model: String,
+    pub prompt: String,
+    pub content: String,
+    #[serde(default)]
+    pub usage: Option<Usage>,
+    pub recorded_at: DateTime<Utc>,
+}
+
+/// Appends LLM answers to a JSONL store from a background task.
+#[derive(Clone)]
+pub struct ResponseRecorder {
+    writer: JsonlWriter<RecordedResponse>,
+}
+
+impl ResponseRecorder {
+    pub fn spawn(path: impl Into<PathBuf>) -> (Self, JoinHandle<()>) {
+        let (writer, handle) = JsonlWriter::spawn("ai_replay", path, QUEUE_CAPACITY);
+        (Self { writer }, handle)
+    }
+
+    pub fn record(&self, model: &str, prompt: &str, content: &str, usage: Option<Usage>) {
+        let response = RecordedResponse {
+            key: request_key(model, prompt),
+            model: model.to_string(),
+            prompt: prompt.to_string(),
+            content: content.to_string(),
+            usage,
+            recorded_at: Utc::now(),
+        };
+        self.writer.send(response);
+    }
+}
+
+/// Recorded answers loaded into memory, keyed by `request_key`.
+/// Later recordings of the same request replace earlier ones.
+#[derive(Debug, Default)]
+pub struct ReplayStore {
+    responses: HashMap<String, RecordedResponse>,
+}
+
+impl ReplayStore {
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let file = std::fs::File::open(path)
+            .with_context(|| format!("Failed to open replay store {}", path.display()))?;
+        let mut store = Self::default();
+        for (line_no, line) in std::io::BufReader::new(file).lines().enumerate() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            match serde_json::from_str::<RecordedResponse>(&line) {
+                Ok(response) => store.insert(response),
+                Err(e) => warn!(line = line_no + 1, error = %e, "Skipping malformed replay line"),
+            }
+        }
+        info!(responses = store.len(), path = %path.display(), "Replay store loaded");
+        Ok(store)
+    }
+
+    pub fn insert(&mut self, response: RecordedResponse) {
+        self.responses.insert(response.key.clone(), response);
+    }
+
+    pub fn get(&self, key: &str) -> Option<&RecordedResponse> {
+        self.responses.get(key)
+    }
+
+    pub fn len(&self) -> usize {
+        self.responses.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.responses.is_empty()
+    }
+}
+
+/// What the replay scorer answers when a request was never recorded
+#[derive(Debug, Clone)]
+pub enum MissPolicy {
+    /// Fail with `AiError::ReplayMiss`
+    Error,
+    /// Score the signal with the heuristic scorer
+    Heuristic(HeuristicScorer),
+    /// Always return this score
+    Fixed(AiScore),
+}
+
+/// Offline scorer that answers from a `ReplayStore` using the exact same
+/// prompt `DeepSeekScorer` would send, so backtests see historical answers.
+pub struct ReplayScorer {
+    store: ReplayStore,
+    model: String,
+    miss_policy: MissPolicy,
+    hits: AtomicU64,
+    misses: AtomicU64,
+}
+
+impl ReplayScorer {
+    pub fn new(store: ReplayStore, model: String, miss_policy: MissPolicy) -> Self {
+        Self {
+            store,
+            model,
+            miss_policy,
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+        }
+    }
+
+    pub fn hits(&self) -> u64 {
+        self.hits.load(Ordering::Relaxed)
+    }
+
+    pub fn misses(&self) -> u64 {
+        self.misses.load(Ordering::Relaxed)
+    }
+
+    fn replay(&self, signal: &MintSignal) -> Result<AiScore> {
+        let prompt = build_prompt(signal);
+        let key = request_key(&self.model, &prompt);
+        if let Some(recorded) = self.store.get(&key) {
+            self.hits.fetch_add(1, Ordering::Relaxed);
+            return Ok(score_from_content(&recorded.content));
+        }
+
+        self.misses.fetch_add(1, Ordering::Relaxed);
+        debug!(mint = %signal.mint_address, key, "Replay miss");
+        match &self.miss_policy {
+            MissPolicy::Error => Err(AiError::ReplayMiss { key }.into()),
+            MissPolicy::Heuristic(heuristic) => Ok(heuristic.score(signal)),
+            MissPolicy::Fixed(score) => Ok(score.clone()),
+        }
+    }
+}
+
+impl Scorer for ReplayScorer {
+    async fn score(&self, signal: &MintSignal) -> Result<AiScore> {
+        self.replay(signal)
+    }
+}
+
+impl AiAnalyzer for ReplayScorer {
+    async fn analyze(&self, signal: &MintSignal) -> Option<ScoredSignal> {
+        let score = self.replay(signal).ok()?;
+        Some(ScoredSignal {
+            signal: signal.clone(),
+            score: score.confidence,
+            should_buy: score.should_buy,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn signal(mint: &str) -> MintSignal {
+        MintSignal::new(
+            mint.to_string(),
+            10_000.0,
+            8_000.0,
+            0.001,
+            200,
+            6_000.0,
+            10.0,
+        )
+    }
+
+    fn recorded(model: &str, signal: &MintSignal, content: &str) -> RecordedResponse {
+        let prompt = build_prompt(signal);
+        RecordedResponse {
+            key: request_key(model, &prompt),
+            model: model.to_string(),
+            prompt,
+            content: content.to_string(),
+            usage: None,
+            recorded_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_request_key_is_stable() {
+        // FNV-1a of "m\0p" must never change, or old recordings stop matching
+        assert_eq!(request_key("m", "p"), "0879531917c7bf68");
+        assert_ne!(request_key("m", "p"), request_key("m2", "p"));
+        assert_eq!(request_key("", ""), "af63bd4c8601b7df");
+    }
+
+    #[tokio::test]
+    async fn test_replay_hit() {
+        let s = signal("mint_a");
+        let mut store = ReplayStore::default();
+        store.insert(recorded(
+            "deepseek-chat",
+            &s,
+            r#"{"confidence": 0.42, "reasoning": "recorded", "should_buy": false}"#,
+        ));
+        let scorer = ReplayScorer::new(store, "deepseek-chat".to_string(), MissPolicy::Error);
+        let score = Scorer::score(&scorer, &s).await.unwrap();
+        assert!((score.confidence - 0.42).abs() < 1e-10);
+        assert_eq!(scorer.hits(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_unparseable_recording_scores_like_live() {
+        let s = signal("mint_c");
+        let mut store = ReplayStore::default();
+        store.insert(recorded("deepseek-chat", &s, "no json here"));
+        let scorer = ReplayScorer::new(store, "deepseek-chat".to_string(), MissPolicy::Error);
+        let score = Scorer::score(&scorer, &s).await.unwrap();
+        assert_eq!(score.confidence, 0.0);
+        assert!(!score.should_buy);
+        assert!(score.reasoning.starts_with("Parse error"));
+    }
+
+    #[tokio::test]
+    async fn test_miss_policies() {
+        let s = signal("unknown");
+        let err = Scorer::score(
+            &ReplayScorer::new(ReplayStore::default(), "m".to_string(), MissPolicy::Error),
+            &s,
+        )
+        .await
+        .unwrap_err();
+        assert!(matches!(
+            err.downcast_ref::<AiError>(),
+            Some(AiError::ReplayMiss { .. })
+        ));
+
+        let fixed = AiScore {
+            confidence: 0.0,
+            reasoning: "miss".to_string(),
+            should_buy: false,
+        };
+        let scorer = ReplayScorer::new(
+            ReplayStore::default(),
+            "m".to_string(),
+            MissPolicy::Fixed(fixed),
+        );
+        assert_eq!(Scorer::score(&scorer, &s).await.unwrap().reasoning, "miss");
+
+        let scorer = ReplayScorer::new(
+            ReplayStore::default(),
+            "m".to_string(),
+            MissPolicy::Heuristic(HeuristicScorer::default()),
+        );
+        let score = Scorer::score(&scorer, &s).await.unwrap();
+        assert!(score.reasoning.starts_with("heuristic"));
+        assert_eq!(scorer.misses(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_record_then_replay_from_disk() {
+        let path = std::env::temp_dir().join(format!("hydra_replay_{}.jsonl", std::process::id()));
+        std::fs::remove_file(&path).ok();
+        let s = signal("mint_b");
+        let (recorder, handle) = ResponseRecorder::spawn(&path);
+        recorder.record(
+            "deepseek-chat",
+            &build_prompt(&s),
+            r#"{"confidence": 0.9, "reasoning": "r", "should_buy": true}"#,
+            None,
+        );
+        drop(recorder);
+        handle.await.unwrap();
+
+        let store = ReplayStore::load(&path).unwrap();
+        let scorer = ReplayScorer::new(store, "deepseek-chat".to_string(), MissPolicy::Error);
+        assert!(Scorer::score(&scorer, &s).await.unwrap().should_buy);
+        std::fs::remove_file(&path).ok();
+    }
+}
@@ -1,9 +1,16 @@
-use anyhow::{Context, Result};
+use anyhow::{ensure, Context, Result};
 
 #[derive(Debug, Clone, serde::Deserialize)]
 pub struct HydraConfig {
     pub deepseek_api_url: String,
     pub deepseek_model: String,
+    pub deepseek_hedge_url: Option<String>,
+    pub ai_hedge_quantile: f64,
+    pub ai_requests_per_minute: u32,
+    pub ai_tokens_per_minute: u32,
+    pub ai_daily_budget_usd: Option<f64>,
+    pub metadata_gateway_url: String,
+    pub metadata_timeout_ms: u64,
     pub telegram_bot_token: Option<String>,
     pub telegram_chat_id: Option<String>,
     pub prometheus_port: u16,
@@ -14,11 +21,35 @@ pub struct HydraConfig {
 
 impl HydraConfig {
     pub fn from_env() -> Result<Self> {
-        Ok(Self {
+        let config = Self {
             deepseek_api_url: std::env::var("DEEPSEEK_API_URL")
                 .unwrap_or_else(|_| "https://api.deepseek.com".to_string()),
             deepseek_model: std::env::var("DEEPSEEK_MODEL")
                 .unwrap_or_else(|_| "deepseek-chat".to_string()),
+            deepseek_hedge_url: std::env::var("DEEPSEEK_HEDGE_URL").ok(),
+            ai_hedge_quantile: std::env::var("AI_HEDGE_QUANTILE")
+                .unwrap_or_else(|_| "0.9".to_string())
+                .parse::<f64>()
+                .context("AI_HEDGE_QUANTILE must be a valid float")?,
+            ai_requests_per_minute: std::env::var("AI_REQUESTS_PER_MINUTE")
+                .unwrap_or_else(|_| "60".to_string())
+                .parse::<u32>()
+                .context("AI_REQUESTS_PER_MINUTE must be a valid integer")?,
+            ai_tokens_per_minute: std::env::var("AI_TOKENS_PER_MINUTE")
+                .unwrap_or_else(|_| "100000".to_string())
+                .parse::<u32>()
+                .context("AI_TOKENS_PER_MINUTE must be a valid integer")?,
+            ai_daily_budget_usd: std::env::var("AI_DAILY_BUDGET_USD")
+                .ok()
+                .map(|v| v.parse::<f64>())
+                .transpose()
+                .context("AI_DAILY_BUDGET_USD must be a valid float")?,
+            metadata_gateway_url: std::env::var("METADATA_GATEWAY_URL")
+                .unwrap_or_else(|_| "https://ipfs.io".to_string()),
+            metadata_timeout_ms: std::env::var("METADATA_TIMEOUT_MS")
+                .unwrap_or_else(|_| "1500".to_string())
+                .parse::<u64>()
+                .context("METADATA_TIMEOUT_MS must be a valid integer")?,
             telegram_bot_token: std::env::var("TELEGRAM_BOT_TOKEN").ok(),
             telegram_chat_id: std::env::var("TELEGRAM_CHAT_ID").ok(),
             prometheus_port: std::env::var("PROMETHEUS_PORT")
@@ -35,6 +66,12 @@ impl HydraConfig {
                 .context("MAX_OPEN_POSITIONS must be a valid integer")?,
             trade_journal_path: std::env::var("TRADE_JOURNAL_PATH")
                 .unwrap_or_else(|_| "logs/trades.csv".to_string()),
-        })
+        };
+        ensure!(
+            (0.0..=1.0).contains(&config.ai_hedge_quantile),
+            "AI_HEDGE_QUANTILE must be between 0 and 1, got {}",
+            config.ai_hedge_quantile
+        );
+        Ok(config)
     }
 }
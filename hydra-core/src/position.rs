@@ -10,6 +10,9 @@ pub struct Position {
     pub take_profit_pct: f64,
     pub stop_loss_pct: f64,
     pub opened_at: DateTime<Utc>,
+    /// Strategy that opened the position
+    #[serde(default)]
+    pub strategy_id: Option<String>,
 }
 
 impl Position {
@@ -29,8 +32,14 @@ impl Position {
             take_profit_pct,
             stop_loss_pct,
             opened_at: Utc::now(),
+            strategy_id: None,
         }
     }
+
+    pub fn with_strategy(mut self, strategy_id: String) -> Self {
+        self.strategy_id = Some(strategy_id);
+        self
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -44,4 +53,6 @@ pub struct CompletedTrade {
     pub opened_at: DateTime<Utc>,
     pub closed_at: DateTime<Utc>,
     pub exit_reason: String,
+    #[serde(default)]
+    pub strategy_id: Option<String>,
 }
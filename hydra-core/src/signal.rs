@@ -1,6 +1,57 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
+/// Off-chain token metadata resolved from the create event `uri`
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct TokenMetadata {
+    #[serde(default)]
+    pub name: String,
+    #[serde(default)]
+    pub symbol: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub image: Option<String>,
+    #[serde(default)]
+    pub twitter: Option<String>,
+    #[serde(default)]
+    pub telegram: Option<String>,
+    #[serde(default)]
+    pub website: Option<String>,
+}
+
+impl TokenMetadata {
+    /// Number of social links (twitter, telegram, website) that are set
+    pub fn social_count(&self) -> usize {
+        [&self.twitter, &self.telegram, &self.website]
+            .into_iter()
+            .filter(|link| link.as_deref().is_some_and(|l| !l.trim().is_empty()))
+            .count()
+    }
+}
+
+/// Cap of `FlowFeatures::buy_sell_volume_ratio`, reported when there
+/// was buying and no selling. Finite so the features stay valid JSON.
+pub const MAX_BUY_SELL_RATIO: f64 = 100.0;
+
+/// Short-window order flow of a mint, derived from its recent trades
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct FlowFeatures {
+    pub window_secs: f64,
+    pub buys_per_sec: f64,
+    pub sells_per_sec: f64,
+    pub unique_buyers: u64,
+    /// Buy SOL volume over sell SOL volume, at most `MAX_BUY_SELL_RATIO`
+    /// (also when nothing was sold) and 0 without trades
+    pub buy_sell_volume_ratio: f64,
+    pub holder_growth_per_min: f64,
+    /// Relative price change per second over the window
+    pub price_velocity: f64,
+    /// Change in velocity between the two halves of the window, per second
+    pub price_acceleration: f64,
+    pub avg_buy_sol: f64,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MintSignal {
     pub mint_address: String,
@@ -11,6 +62,15 @@ pub struct MintSignal {
     pub liquidity_usd: f64,
     pub top_holder_pct: f64,
     pub timestamp: DateTime<Utc>,
+    #[serde(default)]
+    pub uri: Option<String>,
+    #[serde(default)]
+    pub metadata: Option<TokenMetadata>,
+    /// Wallet that created the token
+    #[serde(default)]
+    pub creator: Option<String>,
+    #[serde(default)]
+    pub flow: Option<FlowFeatures>,
 }
 
 impl MintSignal {
@@ -32,6 +92,90 @@ impl MintSignal {
             liquidity_usd,
             top_holder_pct,
             timestamp: Utc::now(),
+            uri: None,
+            metadata: None,
+            creator: None,
+            flow: None,
+        }
+    }
+
+    pub fn with_uri(mut self, uri: String) -> Self {
+        self.uri = Some(uri);
+        self
+    }
+
+    pub fn with_creator(mut self, creator: String) -> Self {
+        self.creator = Some(creator);
+        self
+    }
+
+    pub fn with_flow(mut self, flow: FlowFeatures) -> Self {
+        self.flow = Some(flow);
+        self
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TradeKind {
+    /// Token creation, with the creator's initial buy if any
+    Create,
+    Buy,
+    Sell,
+}
+
+/// One trade on a pump.fun bonding curve
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TradeEvent {
+    pub mint_address: String,
+    pub trader: String,
+    pub kind: TradeKind,
+    pub sol_amount: f64,
+    pub token_amount: f64,
+    pub timestamp: DateTime<Utc>,
+    #[serde(default)]
+    pub slot: Option<u64>,
+}
+
+impl TradeEvent {
+    pub fn new(
+        mint_address: String,
+        trader: String,
+        kind: TradeKind,
+        sol_amount: f64,
+        token_amount: f64,
+    ) -> Self {
+        Self {
+            mint_address,
+            trader,
+            kind,
+            sol_amount,
+            token_amount,
+            timestamp: Utc::now(),
+            slot: None,
+        }
+    }
+
+    pub fn with_slot(mut self, slot: u64) -> Self {
+        self.slot = Some(slot);
+        self
+    }
+}
+
+/// Latest observed price of a mint
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PriceTick {
+    pub mint_address: String,
+    pub price_usd: f64,
+    pub timestamp: DateTime<Utc>,
+}
+
+impl PriceTick {
+    pub fn new(mint_address: String, price_usd: f64) -> Self {
+        Self {
+            mint_address,
+            price_usd,
+            timestamp: Utc::now(),
         }
     }
 }
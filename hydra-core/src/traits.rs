@@ -1,6 +1,7 @@
 // Central interfaces for all crates.
 // Enables mocking in tests!
 
+use crate::position::Position;
 use crate::signal::MintSignal;
 use std::future::Future;
 use thiserror::Error;
@@ -35,6 +36,16 @@ pub trait TradeExecutor: Send + Sync {
     fn execute(&self, signal: &ScoredSignal) -> impl Future<Output = anyhow::Result<()>> + Send;
 }
 
+/// Sells part of an open position, returning the average fill price in USD
+pub trait SellExecutor: Send + Sync {
+    fn sell(
+        &self,
+        position: &Position,
+        fraction: f64,
+        reason: &str,
+    ) -> impl Future<Output = anyhow::Result<f64>> + Send;
+}
+
 /// Checks whether a trade is permitted
 pub trait RiskEngine: Send + Sync {
     fn approve(&self, signal: &MintSignal) -> Result<(), RiskDenied>;
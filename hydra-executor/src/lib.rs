@@ -2,8 +2,10 @@ pub mod fee;
 pub mod retry;
 pub mod shield;
 pub mod tpu;
+pub mod tx;
 
 pub use fee::FeeCalculator;
 pub use retry::RetryPolicy;
 pub use shield::Shield;
 pub use tpu::JetTpuClient;
+pub use tx::{Message, Pubkey, Transaction};
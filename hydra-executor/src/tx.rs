@@ -0,0 +1,990 @@
+use anyhow::{bail, Context, Result};
+use curve25519_dalek::edwards::CompressedEdwardsY;
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::str::FromStr;
+
+// Built-in addresses as bytes, so nothing is parsed at runtime. Tests
+// check each against its base58 form and, for PDAs, its derivation.
+
+/// `11111111111111111111111111111111`
+pub const SYSTEM_PROGRAM_ID: Pubkey = Pubkey([0; 32]);
+
+/// `TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA`
+pub const TOKEN_PROGRAM_ID: Pubkey = Pubkey([
+    6, 221, 246, 225, 215, 101, 161, 147, 217, 203, 225, 70, 206, 235, 121, 172, 28, 180, 133, 237,
+    95, 91, 55, 145, 58, 140, 245, 133, 126, 255, 0, 169,
+]);
+
+/// `ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL`
+pub const ASSOCIATED_TOKEN_PROGRAM_ID: Pubkey = Pubkey([
+    140, 151, 37, 143, 78, 36, 137, 241, 187, 61, 16, 41, 20, 142, 13, 131, 11, 90, 19, 153, 218,
+    255, 16, 132, 4, 142, 123, 216, 219, 233, 248, 89,
+]);
+
+/// `ComputeBudget111111111111111111111111111111`
+pub const COMPUTE_BUDGET_PROGRAM_ID: Pubkey = Pubkey([
+    3, 6, 70, 111, 229, 33, 23, 50, 255, 236, 173, 186, 114, 195, 155, 231, 188, 140, 229, 187,
+    197, 247, 18, 107, 44, 67, 155, 58, 64, 0, 0, 0,
+]);
+
+/// `6EF8rrecthR5Dkzon8Nwu78hRvfCKubJ14M5uBEwF6P`
+pub const PUMP_FUN_PROGRAM_ID: Pubkey = Pubkey([
+    1, 86, 224, 246, 147, 102, 90, 207, 68, 219, 21, 104, 191, 23, 91, 170, 81, 137, 203, 151, 245,
+    210, 255, 59, 101, 93, 43, 182, 253, 109, 24, 176,
+]);
+
+/// `CebN5WGQ4jvEPvsVU4EoHEpgzq1VV7AbicfhtW4xC9iM`
+pub const PUMP_FUN_FEE_RECIPIENT: Pubkey = Pubkey([
+    173, 17, 230, 164, 252, 41, 68, 164, 250, 130, 81, 190, 248, 21, 66, 110, 27, 251, 40, 198,
+    182, 100, 102, 119, 96, 124, 106, 217, 245, 102, 166, 70,
+]);
+
+/// `pfeeUxB6jkeY1Hxd7CsFCAjcbHA9rWtchMGdZ6VojVZ`, which owns the fee config
+pub const PUMP_FUN_FEE_PROGRAM_ID: Pubkey = Pubkey([
+    12, 53, 255, 169, 5, 90, 142, 86, 141, 168, 247, 188, 7, 86, 21, 39, 76, 241, 201, 44, 164, 31,
+    64, 0, 156, 81, 106, 164, 20, 194, 124, 112,
+]);
+
+/// `4wTV1YmiEkRvAtNtsSGPtUrqRYQMe5SKy2uB4Jjaxnjf`, PDA `["global"]`
+pub const PUMP_FUN_GLOBAL: Pubkey = Pubkey([
+    58, 134, 94, 105, 238, 15, 84, 128, 202, 188, 246, 99, 87, 228, 220, 47, 24, 213, 141, 69, 193,
+    234, 116, 137, 251, 55, 35, 217, 121, 60, 114, 166,
+]);
+
+/// `Ce6TQqeHC9p8KetsN6JsjHK7UTZk7nasjjnr7XxXp9F1`, PDA `["__event_authority"]`
+pub const PUMP_FUN_EVENT_AUTHORITY: Pubkey = Pubkey([
+    172, 241, 54, 235, 1, 252, 28, 78, 136, 61, 35, 200, 181, 132, 74, 181, 154, 55, 246, 106, 221,
+    87, 197, 233, 172, 59, 83, 224, 89, 211, 92, 100,
+]);
+
+/// `Hq2wp8uJ9jCPsYgNHex8RtqdvMPfVGoYwjvF1ATiwn2Y`, PDA `["global_volume_accumulator"]`
+pub const PUMP_FUN_GLOBAL_VOLUME_ACCUMULATOR: Pubkey = Pubkey([
+    250, 9, 17, 165, 72, 99, 65, 45, 99, 31, 78, 7, 135, 3, 41, 108, 3, 95, 13, 19, 51, 160, 217,
+    200, 131, 141, 115, 183, 16, 254, 110, 45,
+]);
+
+/// `8Wf5TiAheLUqBrKXeYg2JtAFFMWtKdG2BSFgqUcPVwTt`, PDA `["fee_config", pump.fun]` of the
+/// fee program
+pub const PUMP_FUN_FEE_CONFIG: Pubkey = Pubkey([
+    111, 154, 180, 164, 241, 149, 141, 192, 169, 201, 76, 63, 183, 44, 7, 153, 88, 67, 237, 164,
+    133, 227, 162, 79, 16, 198, 147, 153, 248, 25, 148, 15,
+]);
+
+/// Anchor discriminators of the pump.fun instructions
+const BUY_DISCRIMINATOR: [u8; 8] = [102, 6, 61, 18, 1, 218, 235, 234];
+const SELL_DISCRIMINATOR: [u8; 8] = [51, 230, 133, 164, 1, 127, 131, 173];
+
+const MAX_SEED_LEN: usize = 32;
+const MAX_SEEDS: usize = 16;
+const PDA_MARKER: &[u8] = b"ProgramDerivedAddress";
+
+fn decode_32(s: &str) -> Result<[u8; 32]> {
+    let bytes = bs58::decode(s)
+        .into_vec()
+        .with_context(|| format!("Invalid base58: {s}"))?;
+    bytes
+        .try_into()
+        .map_err(|b: Vec<u8>| anyhow::anyhow!("Expected 32 bytes, got {} in {s}", b.len()))
+}
+
+/// Ed25519 public key or program address
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Pubkey(pub [u8; 32]);
+
+impl Pubkey {
+    pub fn is_on_curve(&self) -> bool {
+        CompressedEdwardsY(self.0).decompress().is_some()
+    }
+
+    /// `None` when the hash lands on the curve, as a signer key could
+    pub fn create_program_address(seeds: &[&[u8]], program_id: &Pubkey) -> Option<Self> {
+        if seeds.len() > MAX_SEEDS || seeds.iter().any(|s| s.len() > MAX_SEED_LEN) {
+            return None;
+        }
+        let mut hasher = Sha256::new();
+        for seed in seeds {
+            hasher.update(seed);
+        }
+        hasher.update(program_id.0);
+        hasher.update(PDA_MARKER);
+        let key = Self(hasher.finalize().into());
+        (!key.is_on_curve()).then_some(key)
+    }
+
+    /// Address and bump for the first bump from 255 down that is off curve
+    pub fn find_program_address(seeds: &[&[u8]], program_id: &Pubkey) -> Result<(Self, u8)> {
+        for bump in (0..=u8::MAX).rev() {
+            let bump_seed = [bump];
+            let mut with_bump = seeds.to_vec();
+            with_bump.push(&bump_seed);
+            if let Some(key) = Self::create_program_address(&with_bump, program_id) {
+                return Ok((key, bump));
+            }
+        }
+        bail!("No viable bump seed for program address under {program_id}")
+    }
+}
+
+impl FromStr for Pubkey {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        decode_32(s).map(Self)
+    }
+}
+
+impl fmt::Display for Pubkey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&bs58::encode(self.0).into_string())
+    }
+}
+
+impl fmt::Debug for Pubkey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+/// Recent blockhash a message is bound to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Blockhash(pub [u8; 32]);
+
+impl FromStr for Blockhash {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        decode_32(s).map(Self)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AccountMeta {
+    pub pubkey: Pubkey,
+    pub is_signer: bool,
+    pub is_writable: bool,
+}
+
+impl AccountMeta {
+    pub fn new(pubkey: Pubkey, is_signer: bool) -> Self {
+        Self {
+            pubkey,
+            is_signer,
+            is_writable: true,
+        }
+    }
+
+    pub fn new_readonly(pubkey: Pubkey, is_signer: bool) -> Self {
+        Self {
+            pubkey,
+            is_signer,
+            is_writable: false,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Instruction {
+    pub program_id: Pubkey,
+    pub accounts: Vec<AccountMeta>,
+    pub data: Vec<u8>,
+}
+
+// ── Addresses ────────────────────────────────────────────────────────────────
+
+fn pump_fun_pda(seeds: &[&[u8]]) -> Result<Pubkey> {
+    Pubkey::find_program_address(seeds, &PUMP_FUN_PROGRAM_ID).map(|(key, _)| key)
+}
+
+pub fn bonding_curve_pda(mint: &Pubkey) -> Result<Pubkey> {
+    pump_fun_pda(&[b"bonding-curve", &mint.0])
+}
+
+/// Where the creator's share of trading fees accrues
+pub fn creator_vault_pda(creator: &Pubkey) -> Result<Pubkey> {
+    pump_fun_pda(&[b"creator-vault", &creator.0])
+}
+
+pub fn user_volume_accumulator_pda(user: &Pubkey) -> Result<Pubkey> {
+    pump_fun_pda(&[b"user_volume_accumulator", &user.0])
+}
+
+/// Associated token account of `owner` for `mint` under the classic
+/// token program
+pub fn associated_token_address(owner: &Pubkey, mint: &Pubkey) -> Result<Pubkey> {
+    Pubkey::find_program_address(
+        &[&owner.0, &TOKEN_PROGRAM_ID.0, &mint.0],
+        &ASSOCIATED_TOKEN_PROGRAM_ID,
+    )
+    .map(|(key, _)| key)
+}
+
+/// The bonding curve's own token account
+pub fn associated_bonding_curve(mint: &Pubkey) -> Result<Pubkey> {
+    associated_token_address(&bonding_curve_pda(mint)?, mint)
+}
+
+// ── Instructions ─────────────────────────────────────────────────────────────
+
+pub fn set_compute_unit_limit(units: u32) -> Instruction {
+    let mut data = vec![2];
+    data.extend_from_slice(&units.to_le_bytes());
+    Instruction {
+        program_id: COMPUTE_BUDGET_PROGRAM_ID,
+        accounts: Vec::new(),
+        data,
+    }
+}
+
+pub fn set_compute_unit_price(micro_lamports: u64) -> Instruction {
+    let mut data = vec![3];
+    data.extend_from_slice(&micro_lamports.to_le_bytes());
+    Instruction {
+        program_id: COMPUTE_BUDGET_PROGRAM_ID,
+        accounts: Vec::new(),
+        data,
+    }
+}
+
+/// Create `owner`'s token account for `mint` unless it already exists
+pub fn create_associated_token_account_idempotent(
+    payer: &Pubkey,
+    owner: &Pubkey,
+    mint: &Pubkey,
+) -> Result<Instruction> {
+    Ok(Instruction {
+        program_id: ASSOCIATED_TOKEN_PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new(*payer, true),
+            AccountMeta::new(associated_token_address(owner, mint)?, false),
+            AccountMeta::new_readonly(*owner, false),
+            AccountMeta::new_readonly(*mint, false),
+            AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+            AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+        ],
+        data: vec![1],
+    })
+}
+
+fn pump_fun_data(discriminator: [u8; 8], amount: u64, sol_limit: u64) -> Vec<u8> {
+    let mut data = discriminator.to_vec();
+    data.extend_from_slice(&amount.to_le_bytes());
+    data.extend_from_slice(&sol_limit.to_le_bytes());
+    data
+}
+
+/// Buy `token_amount` raw tokens (6 decimals), paying at most
+/// `max_sol_cost` lamports. `creator` is the coin creator recorded on the
+/// bonding curve, whose vault takes the creator fee.
+pub fn buy_instruction(
+    mint: &Pubkey,
+    creator: &Pubkey,
+    user: &Pubkey,
+    token_amount: u64,
+    max_sol_cost: u64,
+) -> Result<Instruction> {
+    Ok(Instruction {
+        program_id: PUMP_FUN_PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new_readonly(PUMP_FUN_GLOBAL, false),
+            AccountMeta::new(PUMP_FUN_FEE_RECIPIENT, false),
+            AccountMeta::new_readonly(*mint, false),
+            AccountMeta::new(bonding_curve_pda(mint)?, false),
+            AccountMeta::new(associated_bonding_curve(mint)?, false),
+            AccountMeta::new(associated_token_address(user, mint)?, false),
+            AccountMeta::new(*user, true),
+            AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+            AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+            AccountMeta::new(creator_vault_pda(creator)?, false),
+            AccountMeta::new_readonly(PUMP_FUN_EVENT_AUTHORITY, false),
+            AccountMeta::new_readonly(PUMP_FUN_PROGRAM_ID, false),
+            AccountMeta::new(PUMP_FUN_GLOBAL_VOLUME_ACCUMULATOR, false),
+            AccountMeta::new(user_volume_accumulator_pda(user)?, false),
+            AccountMeta::new_readonly(PUMP_FUN_FEE_CONFIG, false),
+            AccountMeta::new_readonly(PUMP_FUN_FEE_PROGRAM_ID, false),
+        ],
+        data: pump_fun_data(BUY_DISCRIMINATOR, token_amount, max_sol_cost),
+    })
+}
+
+/// Sell `token_amount` raw tokens for at least `min_sol_output` lamports.
+/// Unlike the buy, the creator vault comes before the token program.
+pub fn sell_instruction(
+    mint: &Pubkey,
+    creator: &Pubkey,
+    user: &Pubkey,
+    token_amount: u64,
+    min_sol_output: u64,
+) -> Result<Instruction> {
+    Ok(Instruction {
+        program_id: PUMP_FUN_PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new_readonly(PUMP_FUN_GLOBAL, false),
+            AccountMeta::new(PUMP_FUN_FEE_RECIPIENT, false),
+            AccountMeta::new_readonly(*mint, false),
+            AccountMeta::new(bonding_curve_pda(mint)?, false),
+            AccountMeta::new(associated_bonding_curve(mint)?, false),
+            AccountMeta::new(associated_token_address(user, mint)?, false),
+            AccountMeta::new(*user, true),
+            AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+            AccountMeta::new(creator_vault_pda(creator)?, false),
+            AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+            AccountMeta::new_readonly(PUMP_FUN_EVENT_AUTHORITY, false),
+            AccountMeta::new_readonly(PUMP_FUN_PROGRAM_ID, false),
+            AccountMeta::new_readonly(PUMP_FUN_FEE_CONFIG, false),
+            AccountMeta::new_readonly(PUMP_FUN_FEE_PROGRAM_ID, false),
+        ],
+        data: pump_fun_data(SELL_DISCRIMINATOR, token_amount, min_sol_output),
+    })
+}
+
+/// Compute unit limit and priority fee prepended to a trade
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ComputeBudget {
+    pub unit_limit: u32,
+    pub unit_price_micro_lamports: u64,
+}
+
+/// Compute budget, idempotent ATA creation and the buy, in that order
+pub fn buy_transaction_instructions(
+    mint: &Pubkey,
+    creator: &Pubkey,
+    user: &Pubkey,
+    token_amount: u64,
+    max_sol_cost: u64,
+    budget: ComputeBudget,
+) -> Result<Vec<Instruction>> {
+    Ok(vec![
+        set_compute_unit_limit(budget.unit_limit),
+        set_compute_unit_price(budget.unit_price_micro_lamports),
+        create_associated_token_account_idempotent(user, user, mint)?,
+        buy_instruction(mint, creator, user, token_amount, max_sol_cost)?,
+    ])
+}
+
+/// Compute budget and the sell
+pub fn sell_transaction_instructions(
+    mint: &Pubkey,
+    creator: &Pubkey,
+    user: &Pubkey,
+    token_amount: u64,
+    min_sol_output: u64,
+    budget: ComputeBudget,
+) -> Result<Vec<Instruction>> {
+    Ok(vec![
+        set_compute_unit_limit(budget.unit_limit),
+        set_compute_unit_price(budget.unit_price_micro_lamports),
+        sell_instruction(mint, creator, user, token_amount, min_sol_output)?,
+    ])
+}
+
+// ── Messages ─────────────────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct MessageHeader {
+    pub num_required_signatures: u8,
+    pub num_readonly_signed_accounts: u8,
+    pub num_readonly_unsigned_accounts: u8,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CompiledInstruction {
+    pub program_id_index: u8,
+    pub accounts: Vec<u8>,
+    pub data: Vec<u8>,
+}
+
+/// On-chain address lookup table and the addresses it holds
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AddressLookupTable {
+    pub key: Pubkey,
+    pub addresses: Vec<Pubkey>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MessageAddressTableLookup {
+    pub account_key: Pubkey,
+    pub writable_indexes: Vec<u8>,
+    pub readonly_indexes: Vec<u8>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MessageVersion {
+    Legacy,
+    V0,
+}
+
+/// Compiled message, laid out like the Solana SDK does it: the payer
+/// first, then writable signers, readonly signers, writable and readonly
+/// non-signers, each group sorted by address.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Message {
+    pub version: MessageVersion,
+    pub header: MessageHeader,
+    pub account_keys: Vec<Pubkey>,
+    pub recent_blockhash: Blockhash,
+    pub instructions: Vec<CompiledInstruction>,
+    pub address_table_lookups: Vec<MessageAddressTableLookup>,
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+struct KeyMeta {
+    is_signer: bool,
+    is_writable: bool,
+    is_invoked: bool,
+}
+
+fn collect_keys(payer: &Pubkey, instructions: &[Instruction]) -> BTreeMap<Pubkey, KeyMeta> {
+    let mut keys: BTreeMap<Pubkey, KeyMeta> = BTreeMap::new();
+    for ix in instructions {
+        keys.entry(ix.program_id).or_default().is_invoked = true;
+        for account in &ix.accounts {
+            let meta = keys.entry(account.pubkey).or_default();
+            meta.is_signer |= account.is_signer;
+            meta.is_writable |= account.is_writable;
+        }
+    }
+    keys.remove(payer);
+    keys
+}
+
+impl Message {
+    pub fn compile_legacy(
+        payer: &Pubkey,
+        instructions: &[Instruction],
+        recent_blockhash: Blockhash,
+    ) -> Result<Self> {
+        Self::compile(
+            MessageVersion::Legacy,
+            payer,
+            instructions,
+            recent_blockhash,
+            &[],
+        )
+    }
+
+    /// Version 0 message. Writable and readonly non-signer accounts found
+    /// in `lookup_tables` are loaded from them instead of listed inline;
+    /// signers and invoked programs always stay inline.
+    pub fn compile_v0(
+        payer: &Pubkey,
+        instructions: &[Instruction],
+        recent_blockhash: Blockhash,
+        lookup_tables: &[AddressLookupTable],
+    ) -> Result<Self> {
+        Self::compile(
+            MessageVersion::V0,
+            payer,
+            instructions,
+            recent_blockhash,
+            lookup_tables,
+        )
+    }
+
+    fn compile(
+        version: MessageVersion,
+        payer: &Pubkey,
+        instructions: &[Instruction],
+        recent_blockhash: Blockhash,
+        lookup_tables: &[AddressLookupTable],
+    ) -> Result<Self> {
+        let mut keys = collect_keys(payer, instructions);
+
+        let mut lookups = Vec::new();
+        let mut loaded_writable = Vec::new();
+        let mut loaded_readonly = Vec::new();
+        for table in lookup_tables {
+            let mut lookup = MessageAddressTableLookup {
+                account_key: table.key,
+                writable_indexes: Vec::new(),
+                readonly_indexes: Vec::new(),
+            };
+            for writable in [true, false] {
+                let found: Vec<(Pubkey, u8)> = keys
+                    .iter()
+                    .filter(|(_, m)| !m.is_signer && !m.is_invoked && m.is_writable == writable)
+                    .filter_map(|(key, _)| {
+                        let index = table.addresses.iter().position(|a| a == key)?;
+                        Some((*key, u8::try_from(index).ok()?))
+                    })
+                    .collect();
+                for (key, index) in found {
+                    keys.remove(&key);
+                    if writable {
+                        lookup.writable_indexes.push(index);
+                        loaded_writable.push(key);
+                    } else {
+                        lookup.readonly_indexes.push(index);
+                        loaded_readonly.push(key);
+                    }
+                }
+            }
+            if !lookup.writable_indexes.is_empty() || !lookup.readonly_indexes.is_empty() {
+                lookups.push(lookup);
+            }
+        }
+
+        let group = |signer: bool, writable: bool| -> Vec<Pubkey> {
+            keys.iter()
+                .filter(|(_, m)| m.is_signer == signer && m.is_writable == writable)
+                .map(|(key, _)| *key)
+                .collect()
+        };
+        let writable_signers = group(true, true);
+        let readonly_signers = group(true, false);
+        let writable_unsigned = group(false, true);
+        let readonly_unsigned = group(false, false);
+
+        let header = MessageHeader {
+            num_required_signatures: u8::try_from(
+                1 + writable_signers.len() + readonly_signers.len(),
+            )
+            .context("Too many signers")?,
+            num_readonly_signed_accounts: readonly_signers.len() as u8,
+            num_readonly_unsigned_accounts: u8::try_from(readonly_unsigned.len())
+                .context("Too many readonly accounts")?,
+        };
+        let account_keys: Vec<Pubkey> = std::iter::once(*payer)
+            .chain(writable_signers)
+            .chain(readonly_signers)
+            .chain(writable_unsigned)
+            .chain(readonly_unsigned)
+            .collect();
+
+        // Instruction indexes address static keys first, then loaded ones
+        let all_keys: Vec<Pubkey> = account_keys
+            .iter()
+            .chain(&loaded_writable)
+            .chain(&loaded_readonly)
+            .copied()
+            .collect();
+        if all_keys.len() > 256 {
+            bail!(
+                "Message references {} accounts, at most 256 fit",
+                all_keys.len()
+            );
+        }
+        let index_of = |key: &Pubkey| -> Result<u8> {
+            let index = all_keys
+                .iter()
+                .position(|k| k == key)
+                .with_context(|| format!("{key} is missing from the account keys"))?;
+            Ok(index as u8)
+        };
+        let instructions = instructions
+            .iter()
+            .map(|ix| {
+                Ok(CompiledInstruction {
+                    program_id_index: index_of(&ix.program_id)?,
+                    accounts: ix
+                        .accounts
+                        .iter()
+                        .map(|a| index_of(&a.pubkey))
+                        .collect::<Result<_>>()?,
+                    data: ix.data.clone(),
+                })
+            })
+            .collect::<Result<_>>()?;
+
+        Ok(Self {
+            version,
+            header,
+            account_keys,
+            recent_blockhash,
+            instructions,
+            address_table_lookups: lookups,
+        })
+    }
+
+    /// Wire format, the bytes that get signed
+    pub fn serialize(&self) -> Vec<u8> {
+        let mut out = Vec::new();
+        if self.version == MessageVersion::V0 {
+            out.push(0x80);
+        }
+        out.extend_from_slice(&[
+            self.header.num_required_signatures,
+            self.header.num_readonly_signed_accounts,
+            self.header.num_readonly_unsigned_accounts,
+        ]);
+        write_compact_u16(&mut out, self.account_keys.len());
+        for key in &self.account_keys {
+            out.extend_from_slice(&key.0);
+        }
+        out.extend_from_slice(&self.recent_blockhash.0);
+        write_compact_u16(&mut out, self.instructions.len());
+        for ix in &self.instructions {
+            out.push(ix.program_id_index);
+            write_compact_u16(&mut out, ix.accounts.len());
+            out.extend_from_slice(&ix.accounts);
+            write_compact_u16(&mut out, ix.data.len());
+            out.extend_from_slice(&ix.data);
+        }
+        if self.version == MessageVersion::V0 {
+            write_compact_u16(&mut out, self.address_table_lookups.len());
+            for lookup in &self.address_table_lookups {
+                out.extend_from_slice(&lookup.account_key.0);
+                write_compact_u16(&mut out, lookup.writable_indexes.len());
+                out.extend_from_slice(&lookup.writable_indexes);
+                write_compact_u16(&mut out, lookup.readonly_indexes.len());
+                out.extend_from_slice(&lookup.readonly_indexes);
+            }
+        }
+        out
+    }
+}
+
+/// A message with one signature slot per required signer. Signing is
+/// left to the wallet; the serialized form is what
+/// `JetTpuClient::send_transaction` takes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Transaction {
+    pub signatures: Vec<[u8; 64]>,
+    pub message: Message,
+}
+
+impl Transaction {
+    pub fn new_unsigned(message: Message) -> Self {
+        Self {
+            signatures: vec![[0; 64]; message.header.num_required_signatures as usize],
+            message,
+        }
+    }
+
+    /// Place the signature of `signer` over `message.serialize()`
+    pub fn add_signature(&mut self, signer: &Pubkey, signature: [u8; 64]) -> Result<()> {
+        let Some(index) = self
+            .message
+            .account_keys
+            .iter()
+            .take(self.signatures.len())
+            .position(|k| k == signer)
+        else {
+            bail!("{signer} is not a signer of this message");
+        };
+        self.signatures[index] = signature;
+        Ok(())
+    }
+
+    pub fn serialize(&self) -> Vec<u8> {
+        let mut out = Vec::new();
+        write_compact_u16(&mut out, self.signatures.len());
+        for signature in &self.signatures {
+            out.extend_from_slice(signature);
+        }
+        out.extend_from_slice(&self.message.serialize());
+        out
+    }
+}
+
+/// Solana's short-vec length prefix: 7 bits per byte, high bit set when
+/// another byte follows
+fn write_compact_u16(out: &mut Vec<u8>, len: usize) {
+    let mut value = len as u16;
+    loop {
+        let mut byte = (value & 0x7f) as u8;
+        value >>= 7;
+        if value == 0 {
+            out.push(byte);
+            return;
+        }
+        byte |= 0x80;
+        out.push(byte);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(byte: u8) -> Pubkey {
+        Pubkey([byte; 32])
+    }
+
+    #[test]
+    fn test_builtin_addresses_match_base58() {
+        for (key, base58) in [
+            (SYSTEM_PROGRAM_ID, "11111111111111111111111111111111"),
+            (
+                TOKEN_PROGRAM_ID,
+                "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA",
+            ),
+            (
+                ASSOCIATED_TOKEN_PROGRAM_ID,
+                "ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL",
+            ),
+            (
+                COMPUTE_BUDGET_PROGRAM_ID,
+                "ComputeBudget111111111111111111111111111111",
+            ),
+            (
+                PUMP_FUN_PROGRAM_ID,
+                "6EF8rrecthR5Dkzon8Nwu78hRvfCKubJ14M5uBEwF6P",
+            ),
+            (
+                PUMP_FUN_FEE_RECIPIENT,
+                "CebN5WGQ4jvEPvsVU4EoHEpgzq1VV7AbicfhtW4xC9iM",
+            ),
+            (
+                PUMP_FUN_FEE_PROGRAM_ID,
+                "pfeeUxB6jkeY1Hxd7CsFCAjcbHA9rWtchMGdZ6VojVZ",
+            ),
+        ] {
+            assert_eq!(key, base58.parse().unwrap());
+        }
+    }
+
+    #[test]
+    fn test_pump_fun_pdas_match_mainnet() {
+        let pda = |seeds: &[&[u8]], program: &Pubkey| {
+            Pubkey::find_program_address(seeds, program)
+                .unwrap()
+                .0
+                .to_string()
+        };
+        let pump = &PUMP_FUN_PROGRAM_ID;
+        for (seeds, key, mainnet) in [
+            (
+                &[b"global".as_slice()][..],
+                PUMP_FUN_GLOBAL,
+                "4wTV1YmiEkRvAtNtsSGPtUrqRYQMe5SKy2uB4Jjaxnjf",
+            ),
+            (
+                &[b"__event_authority".as_slice()][..],
+                PUMP_FUN_EVENT_AUTHORITY,
+                "Ce6TQqeHC9p8KetsN6JsjHK7UTZk7nasjjnr7XxXp9F1",
+            ),
+            (
+                &[b"global_volume_accumulator".as_slice()][..],
+                PUMP_FUN_GLOBAL_VOLUME_ACCUMULATOR,
+                "Hq2wp8uJ9jCPsYgNHex8RtqdvMPfVGoYwjvF1ATiwn2Y",
+            ),
+        ] {
+            assert_eq!(pda(seeds, pump), mainnet);
+            assert_eq!(key.to_string(), mainnet);
+        }
+        assert_eq!(
+            pda(&[b"fee_config", &pump.0], &PUMP_FUN_FEE_PROGRAM_ID),
+            "8Wf5TiAheLUqBrKXeYg2JtAFFMWtKdG2BSFgqUcPVwTt"
+        );
+        assert_eq!(
+            PUMP_FUN_FEE_CONFIG.to_string(),
+            "8Wf5TiAheLUqBrKXeYg2JtAFFMWtKdG2BSFgqUcPVwTt"
+        );
+        // Signs every pump.fun mint, so any token's mint authority shows it
+        assert_eq!(
+            pda(&[b"mint-authority"], pump),
+            "TSLvdd1pWpHVjahSpsvCXUbgwsL3JAcvokwaKt1eokM"
+        );
+
+        let mint = key(7);
+        let curve = bonding_curve_pda(&mint).unwrap();
+        assert!(!curve.is_on_curve());
+        assert_eq!(
+            associated_bonding_curve(&mint).unwrap(),
+            associated_token_address(&curve, &mint).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_instruction_data_layout() {
+        assert_eq!(
+            set_compute_unit_limit(200_000).data,
+            [2, 0x40, 0x0d, 0x03, 0]
+        );
+        assert_eq!(
+            set_compute_unit_price(1_000).data,
+            [3, 0xe8, 0x03, 0, 0, 0, 0, 0, 0]
+        );
+
+        let (mint, creator, user) = (key(7), key(8), key(9));
+        let vault = AccountMeta::new(creator_vault_pda(&creator).unwrap(), false);
+        let buy = buy_instruction(&mint, &creator, &user, 1_000_000, 5).unwrap();
+        assert_eq!(&buy.data[..8], &BUY_DISCRIMINATOR);
+        assert_eq!(&buy.data[8..16], &1_000_000u64.to_le_bytes());
+        assert_eq!(&buy.data[16..], &5u64.to_le_bytes());
+        assert_eq!(buy.accounts.len(), 16);
+        assert_eq!(buy.accounts[6], AccountMeta::new(user, true));
+        assert_eq!(buy.accounts[8].pubkey, TOKEN_PROGRAM_ID);
+        assert_eq!(buy.accounts[9], vault);
+        assert_eq!(
+            buy.accounts[13].pubkey,
+            user_volume_accumulator_pda(&user).unwrap()
+        );
+        assert_eq!(buy.accounts[15].pubkey, PUMP_FUN_FEE_PROGRAM_ID);
+
+        let sell = sell_instruction(&mint, &creator, &user, 1_000_000, 5).unwrap();
+        assert_eq!(&sell.data[..8], &SELL_DISCRIMINATOR);
+        assert_eq!(sell.accounts.len(), 14);
+        assert_eq!(sell.accounts[8], vault);
+        assert_eq!(sell.accounts[9].pubkey, TOKEN_PROGRAM_ID);
+        assert_eq!(sell.accounts[11].pubkey, PUMP_FUN_PROGRAM_ID);
+        assert_eq!(sell.accounts[12].pubkey, PUMP_FUN_FEE_CONFIG);
+    }
+
+    #[test]
+    fn test_legacy_message_bytes() {
+        let payer = key(1);
+        let blockhash = Blockhash([0xaa; 32]);
+        let ix = Instruction {
+            program_id: key(5),
+            accounts: vec![
+                AccountMeta::new_readonly(key(4), false),
+                AccountMeta::new(key(3), false),
+                AccountMeta::new(payer, true),
+            ],
+            data: vec![9, 8],
+        };
+        let message = Message::compile_legacy(&payer, &[ix], blockhash).unwrap();
+
+        let mut expected = vec![1, 0, 2, 4];
+        for byte in [1, 3, 4, 5] {
+            expected.extend_from_slice(&[byte; 32]);
+        }
+        expected.extend_from_slice(&[0xaa; 32]);
+        expected.extend_from_slice(&[1, 3, 3, 2, 1, 0, 2, 9, 8]);
+        assert_eq!(message.serialize(), expected);
+
+        let tx = Transaction::new_unsigned(message);
+        let bytes = tx.serialize();
+        assert_eq!(bytes[0], 1);
+        assert_eq!(&bytes[1..65], &[0; 64]);
+        assert_eq!(&bytes[65..], &expected[..]);
+    }
+
+    #[test]
+    fn test_v0_message_loads_from_lookup_table() {
+        let payer = key(1);
+        let table = AddressLookupTable {
+            key: key(0xee),
+            addresses: vec![key(6), key(4), key(3)],
+        };
+        let ix = Instruction {
+            program_id: key(5),
+            accounts: vec![
+                AccountMeta::new_readonly(key(4), false),
+                AccountMeta::new(key(3), false),
+                AccountMeta::new(key(2), true),
+            ],
+            data: vec![7],
+        };
+        let message = Message::compile_v0(&payer, &[ix], Blockhash([0xbb; 32]), &[table]).unwrap();
+        assert_eq!(message.account_keys, [key(1), key(2), key(5)]);
+
+        let mut expected = vec![0x80, 2, 0, 1, 3];
+        for byte in [1, 2, 5] {
+            expected.extend_from_slice(&[byte; 32]);
+        }
+        expected.extend_from_slice(&[0xbb; 32]);
+        // Static keys 0..3, then loaded writable key(3), readonly key(4)
+        expected.extend_from_slice(&[1, 2, 3, 4, 3, 1, 1, 7]);
+        expected.push(1);
+        expected.extend_from_slice(&[0xee; 32]);
+        expected.extend_from_slice(&[1, 2, 1, 1]);
+        assert_eq!(message.serialize(), expected);
+    }
+
+    fn fixture(hex: &str) -> Vec<u8> {
+        let hex = hex.trim();
+        (0..hex.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).unwrap())
+            .collect()
+    }
+
+    /// Whole buy and sell transactions, against bytes from the
+    /// independent encoder in `testdata/gen_tx_fixtures.py`
+    #[test]
+    fn test_trade_transactions_match_reference_encoder() {
+        let (mint, creator, user) = (key(7), key(8), key(9));
+        let blockhash = Blockhash([0xaa; 32]);
+        let table = AddressLookupTable {
+            key: key(0xee),
+            addresses: vec![
+                SYSTEM_PROGRAM_ID,
+                PUMP_FUN_GLOBAL,
+                PUMP_FUN_FEE_RECIPIENT,
+                PUMP_FUN_EVENT_AUTHORITY,
+                PUMP_FUN_GLOBAL_VOLUME_ACCUMULATOR,
+                PUMP_FUN_FEE_CONFIG,
+                TOKEN_PROGRAM_ID,
+            ],
+        };
+        let budget = |unit_limit| ComputeBudget {
+            unit_limit,
+            unit_price_micro_lamports: 50_000,
+        };
+        let buy = buy_transaction_instructions(
+            &mint,
+            &creator,
+            &user,
+            1_000_000_000,
+            10_000_000,
+            budget(200_000),
+        )
+        .unwrap();
+        let sell = sell_transaction_instructions(
+            &mint,
+            &creator,
+            &user,
+            1_000_000_000,
+            9_000_000,
+            budget(120_000),
+        )
+        .unwrap();
+
+        let tables = std::slice::from_ref(&table);
+        for (ixs, legacy, v0) in [
+            (
+                &buy,
+                include_str!("../testdata/buy_legacy.hex"),
+                include_str!("../testdata/buy_v0.hex"),
+            ),
+            (
+                &sell,
+                include_str!("../testdata/sell_legacy.hex"),
+                include_str!("../testdata/sell_v0.hex"),
+            ),
+        ] {
+            let message = Message::compile_legacy(&user, ixs, blockhash).unwrap();
+            assert_eq!(
+                Transaction::new_unsigned(message).serialize(),
+                fixture(legacy)
+            );
+            let message = Message::compile_v0(&user, ixs, blockhash, tables).unwrap();
+            assert_eq!(Transaction::new_unsigned(message).serialize(), fixture(v0));
+        }
+    }
+
+    #[test]
+    fn test_signatures_and_compact_lengths() {
+        let payer = key(1);
+        let ixs = buy_transaction_instructions(
+            &key(7),
+            &key(8),
+            &payer,
+            1,
+            1,
+            ComputeBudget {
+                unit_limit: 100_000,
+                unit_price_micro_lamports: 10,
+            },
+        )
+        .unwrap();
+        let message = Message::compile_legacy(&payer, &ixs, Blockhash::default()).unwrap();
+        assert_eq!(message.header.num_required_signatures, 1);
+        assert_eq!(message.account_keys[0], payer);
+
+        let mut tx = Transaction::new_unsigned(message);
+        tx.add_signature(&payer, [5; 64]).unwrap();
+        assert_eq!(tx.signatures[0], [5; 64]);
+        assert!(tx.add_signature(&key(7), [5; 64]).is_err());
+
+        let mut out = Vec::new();
+        write_compact_u16(&mut out, 0x7f);
+        write_compact_u16(&mut out, 0x80);
+        write_compact_u16(&mut out, 0x3fff);
+        assert_eq!(out, [0x7f, 0x80, 0x01, 0xff, 0x7f]);
+    }
+}
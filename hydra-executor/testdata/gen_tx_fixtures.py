@@ -0,0 +1,186 @@
+#!/usr/bin/env python3
+"""Reference encoder for the pump.fun transaction fixtures.
+
+Written independently of `src/tx.rs` from the Solana SDK's rules
+(`CompiledKeys`, `try_extract_table_lookup`, short-vec, PDA derivation)
+so the Rust encoder is checked against a second implementation:
+
+    python3 testdata/gen_tx_fixtures.py
+"""
+import hashlib
+import pathlib
+
+ALPHABET = "123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz"
+
+
+def b58(s):
+    n = 0
+    for c in s:
+        n = n * 58 + ALPHABET.index(c)
+    raw = n.to_bytes(32, "big") if n else b""
+    pad = len(s) - len(s.lstrip("1"))
+    out = b"\0" * pad + raw.lstrip(b"\0")
+    assert len(out) == 32, s
+    return out
+
+
+# ed25519 point decompression, RFC 8032 section 5.1.3
+P = 2**255 - 19
+D = (-121665 * pow(121666, P - 2, P)) % P
+
+
+def on_curve(key):
+    y = int.from_bytes(key, "little") & ((1 << 255) - 1)
+    if y >= P:
+        return False
+    u = (y * y - 1) % P
+    v = (D * y * y + 1) % P
+    x = (u * pow(v, 3, P) * pow(u * pow(v, 7, P), (P - 5) // 8, P)) % P
+    if (v * x * x - u) % P == 0:
+        return True
+    return (v * x * x + u) % P == 0
+
+
+def pda(seeds, program):
+    for bump in range(255, -1, -1):
+        h = hashlib.sha256(b"".join(seeds) + bytes([bump]) + program + b"ProgramDerivedAddress").digest()
+        if not on_curve(h):
+            return h
+    raise ValueError("no viable bump")
+
+
+SYSTEM = b58("11111111111111111111111111111111")
+TOKEN = b58("TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA")
+ATA = b58("ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL")
+BUDGET = b58("ComputeBudget111111111111111111111111111111")
+PUMP = b58("6EF8rrecthR5Dkzon8Nwu78hRvfCKubJ14M5uBEwF6P")
+FEE_RECIPIENT = b58("CebN5WGQ4jvEPvsVU4EoHEpgzq1VV7AbicfhtW4xC9iM")
+FEE_PROGRAM = b58("pfeeUxB6jkeY1Hxd7CsFCAjcbHA9rWtchMGdZ6VojVZ")
+GLOBAL = b58("4wTV1YmiEkRvAtNtsSGPtUrqRYQMe5SKy2uB4Jjaxnjf")
+EVENT_AUTHORITY = b58("Ce6TQqeHC9p8KetsN6JsjHK7UTZk7nasjjnr7XxXp9F1")
+GLOBAL_VOLUME = b58("Hq2wp8uJ9jCPsYgNHex8RtqdvMPfVGoYwjvF1ATiwn2Y")
+FEE_CONFIG = b58("8Wf5TiAheLUqBrKXeYg2JtAFFMWtKdG2BSFgqUcPVwTt")
+BUY = hashlib.sha256(b"global:buy").digest()[:8]
+SELL = hashlib.sha256(b"global:sell").digest()[:8]
+
+
+def ata(owner, mint):
+    return pda([owner, TOKEN, mint], ATA)
+
+
+def trade(disc, mint, creator, user, amount, limit, buy):
+    curve = pda([b"bonding-curve", mint], PUMP)
+    vault = (pda([b"creator-vault", creator], PUMP), False, True)
+    token = (TOKEN, False, False)
+    accounts = [
+        (GLOBAL, False, False),
+        (FEE_RECIPIENT, False, True),
+        (mint, False, False),
+        (curve, False, True),
+        (ata(curve, mint), False, True),
+        (ata(user, mint), False, True),
+        (user, True, True),
+        (SYSTEM, False, False),
+    ]
+    accounts += [token, vault] if buy else [vault, token]
+    accounts += [(EVENT_AUTHORITY, False, False), (PUMP, False, False)]
+    if buy:
+        accounts += [
+            (GLOBAL_VOLUME, False, True),
+            (pda([b"user_volume_accumulator", user], PUMP), False, True),
+        ]
+    accounts += [(FEE_CONFIG, False, False), (FEE_PROGRAM, False, False)]
+    data = disc + amount.to_bytes(8, "little") + limit.to_bytes(8, "little")
+    return (PUMP, accounts, data)
+
+
+def budget(units, price):
+    return [
+        (BUDGET, [], b"\x02" + units.to_bytes(4, "little")),
+        (BUDGET, [], b"\x03" + price.to_bytes(8, "little")),
+    ]
+
+
+def shortvec(n):
+    out = b""
+    while True:
+        byte = n & 0x7F
+        n >>= 7
+        if n == 0:
+            return out + bytes([byte])
+        out += bytes([byte | 0x80])
+
+
+def compile_message(payer, instructions, blockhash, tables=None):
+    # CompiledKeys: payer first, the rest ordered by address within groups
+    meta = {payer: [True, True, False]}
+    for program, accounts, _ in instructions:
+        meta.setdefault(program, [False, False, False])[2] = True
+        for key, signer, writable in accounts:
+            m = meta.setdefault(key, [False, False, False])
+            m[0] |= signer
+            m[1] |= writable
+    rest = {k: v for k, v in meta.items() if k != payer}
+
+    lookups, loaded_w, loaded_r = [], [], []
+    for table_key, addresses in tables or []:
+        found = {}
+        for writable in (True, False):
+            idx = []
+            for key in sorted(rest):
+                s, w, invoked = rest[key]
+                if not s and not invoked and w == writable and key in addresses:
+                    idx.append((key, addresses.index(key)))
+            for key, _ in idx:
+                del rest[key]
+            found[writable] = idx
+        if found[True] or found[False]:
+            lookups.append((table_key, [i for _, i in found[True]], [i for _, i in found[False]]))
+            loaded_w += [k for k, _ in found[True]]
+            loaded_r += [k for k, _ in found[False]]
+
+    def group(s, w):
+        return [k for k in sorted(rest) if rest[k][0] == s and rest[k][1] == w]
+
+    ws, rs, wu, ru = group(True, True), group(True, False), group(False, True), group(False, False)
+    keys = [payer] + ws + rs + wu + ru
+    index = keys + loaded_w + loaded_r
+    out = b"" if tables is None else b"\x80"
+    out += bytes([1 + len(ws) + len(rs), len(rs), len(ru)])
+    out += shortvec(len(keys)) + b"".join(keys) + blockhash
+    out += shortvec(len(instructions))
+    for program, accounts, data in instructions:
+        out += bytes([index.index(program)])
+        out += shortvec(len(accounts)) + bytes(index.index(k) for k, _, _ in accounts)
+        out += shortvec(len(data)) + data
+    if tables is not None:
+        out += shortvec(len(lookups))
+        for table_key, w, r in lookups:
+            out += table_key + shortvec(len(w)) + bytes(w) + shortvec(len(r)) + bytes(r)
+    return 1 + len(ws) + len(rs), out
+
+
+def transaction(signers, message):
+    return shortvec(signers) + b"\0" * 64 * signers + message
+
+
+MINT, CREATOR, USER = bytes([7]) * 32, bytes([8]) * 32, bytes([9]) * 32
+BLOCKHASH = bytes([0xAA]) * 32
+TABLE = (bytes([0xEE]) * 32, [SYSTEM, GLOBAL, FEE_RECIPIENT, EVENT_AUTHORITY, GLOBAL_VOLUME, FEE_CONFIG, TOKEN])
+
+buy = budget(200_000, 50_000) + [
+    (ATA, [(USER, True, True), (ata(USER, MINT), False, True), (USER, False, False),
+           (MINT, False, False), (SYSTEM, False, False), (TOKEN, False, False)], b"\x01"),
+    trade(BUY, MINT, CREATOR, USER, 1_000_000_000, 10_000_000, True),
+]
+sell = budget(120_000, 50_000) + [trade(SELL, MINT, CREATOR, USER, 1_000_000_000, 9_000_000, False)]
+
+here = pathlib.Path(__file__).parent
+for name, ixs, tables in [
+    ("buy_legacy", buy, None),
+    ("buy_v0", buy, [TABLE]),
+    ("sell_legacy", sell, None),
+    ("sell_v0", sell, [TABLE]),
+]:
+    signers, message = compile_message(USER, ixs, BLOCKHASH, tables)
+    (here / f"{name}.hex").write_text(transaction(signers, message).hex() + "\n")
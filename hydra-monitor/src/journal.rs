@@ -1,9 +1,10 @@
-use anyhow::Result;
-use csv::WriterBuilder;
+use anyhow::{Context, Result};
+use csv::{ReaderBuilder, WriterBuilder};
 use hydra_core::position::CompletedTrade;
 use std::path::Path;
-use tracing::info;
+use tracing::{info, warn};
 
+#[derive(Clone)]
 pub struct TradeJournal {
     path: String,
 }
@@ -20,7 +21,12 @@ impl TradeJournal {
             std::fs::create_dir_all(parent)?;
         }
 
-        let file_exists = path.exists();
+        // An empty file still needs its header
+        let has_rows = |path: &Path| path.metadata().is_ok_and(|m| m.len() > 0);
+        if has_rows(path) && !self.has_current_header(trade)? {
+            self.migrate()?;
+        }
+        let file_exists = has_rows(path);
         let file = std::fs::OpenOptions::new()
             .create(true)
             .append(true)
@@ -40,4 +46,144 @@ impl TradeJournal {
         );
         Ok(())
     }
+
+    /// Whether the journal's header lists the columns `trade` is written
+    /// with. Journals from before a column was added do not.
+    fn has_current_header(&self, trade: &CompletedTrade) -> Result<bool> {
+        let mut current = WriterBuilder::new()
+            .has_headers(true)
+            .from_writer(Vec::new());
+        current.serialize(trade)?;
+        let current = current
+            .into_inner()
+            .context("Failed to build journal header")?;
+        let current = current.split(|b| *b == b'\n').next().unwrap_or_default();
+
+        let mut reader = ReaderBuilder::new()
+            .has_headers(true)
+            .from_path(&self.path)
+            .with_context(|| format!("Failed to open trade journal {}", self.path))?;
+        let header = reader.byte_headers()?;
+        let mut existing = WriterBuilder::new().from_writer(Vec::new());
+        existing.write_byte_record(header)?;
+        let existing = existing
+            .into_inner()
+            .context("Failed to read journal header")?;
+        Ok(existing.strip_suffix(b"\n").unwrap_or(&existing) == current)
+    }
+
+    /// Rewrite the journal with the current columns, keeping every trade
+    fn migrate(&self) -> Result<()> {
+        let trades = self.load()?;
+        let path = Path::new(&self.path);
+        let tmp = path.with_extension("csv.tmp");
+        let mut writer = WriterBuilder::new()
+            .has_headers(true)
+            .from_path(&tmp)
+            .with_context(|| format!("Failed to create {}", tmp.display()))?;
+        for trade in &trades {
+            writer.serialize(trade)?;
+        }
+        writer.flush()?;
+        drop(writer);
+        std::fs::rename(&tmp, path)
+            .with_context(|| format!("Failed to replace trade journal {}", self.path))?;
+        warn!(path = %self.path, trades = trades.len(), "Trade journal migrated to current columns");
+        Ok(())
+    }
+
+    /// Read every trade recorded so far. A missing journal yields no trades.
+    /// Rows written before a column existed read it as its default.
+    pub fn load(&self) -> Result<Vec<CompletedTrade>> {
+        let path = Path::new(&self.path);
+        if !path.exists() {
+            return Ok(Vec::new());
+        }
+        let mut reader = ReaderBuilder::new()
+            .has_headers(true)
+            .flexible(true)
+            .from_path(path)
+            .with_context(|| format!("Failed to open trade journal {}", self.path))?;
+        let mut trades = Vec::new();
+        for row in reader.deserialize() {
+            trades.push(row.context("Failed to parse trade journal row")?);
+        }
+        Ok(trades)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+
+    #[test]
+    fn test_record_and_load_roundtrip() {
+        let path = std::env::temp_dir().join(format!("hydra_journal_{}.csv", std::process::id()));
+        std::fs::remove_file(&path).ok();
+        let journal = TradeJournal::new(path.to_string_lossy().to_string());
+        let now = Utc::now();
+        for (id, pnl) in [("p1", 0.5), ("p2", -0.2)] {
+            journal
+                .record(&CompletedTrade {
+                    position_id: id.to_string(),
+                    mint_address: "mint".to_string(),
+                    entry_price_usd: 0.001,
+                    exit_price_usd: 0.0012,
+                    size_sol: 1.0,
+                    pnl_sol: pnl,
+                    opened_at: now,
+                    closed_at: now,
+                    exit_reason: "take_profit".to_string(),
+                    strategy_id: (id == "p2").then(|| "aggressive".to_string()),
+                })
+                .unwrap();
+        }
+        let trades = journal.load().unwrap();
+        assert_eq!(trades.len(), 2);
+        assert_eq!(trades[1].position_id, "p2");
+        assert!((trades[1].pnl_sol - (-0.2)).abs() < 1e-10);
+        assert_eq!(trades[0].strategy_id, None);
+        assert_eq!(trades[1].strategy_id.as_deref(), Some("aggressive"));
+        std::fs::remove_file(&path).ok();
+    }
+
+    #[test]
+    fn test_appending_to_journal_without_strategy_column() {
+        let path =
+            std::env::temp_dir().join(format!("hydra_journal_old_{}.csv", std::process::id()));
+        std::fs::write(
+            &path,
+            "position_id,mint_address,entry_price_usd,exit_price_usd,size_sol,pnl_sol,opened_at,closed_at,exit_reason\n\
+             p0,mint,0.001,0.0012,1.0,0.2,2024-01-01T00:00:00Z,2024-01-01T00:05:00Z,take_profit\n",
+        )
+        .unwrap();
+        let journal = TradeJournal::new(path.to_string_lossy().to_string());
+        assert_eq!(journal.load().unwrap()[0].strategy_id, None);
+
+        let now = Utc::now();
+        journal
+            .record(&CompletedTrade {
+                position_id: "p1".to_string(),
+                mint_address: "mint".to_string(),
+                entry_price_usd: 0.001,
+                exit_price_usd: 0.0009,
+                size_sol: 1.0,
+                pnl_sol: -0.1,
+                opened_at: now,
+                closed_at: now,
+                exit_reason: "stop_loss".to_string(),
+                strategy_id: Some("conservative".to_string()),
+            })
+            .unwrap();
+
+        let trades = journal.load().unwrap();
+        assert_eq!(trades.len(), 2);
+        assert_eq!(trades[0].position_id, "p0");
+        assert!((trades[0].pnl_sol - 0.2).abs() < 1e-10);
+        assert_eq!(trades[1].strategy_id.as_deref(), Some("conservative"));
+        let header = std::fs::read_to_string(&path).unwrap();
+        assert!(header.lines().next().unwrap().ends_with(",strategy_id"));
+        std::fs::remove_file(&path).ok();
+    }
 }
@@ -93,7 +93,12 @@ pub struct MetricsServer {
 
 impl MetricsServer {
     pub fn new(port: u16) -> (Self, HydraMetrics) {
-        let mut registry = Registry::default();
+        Self::with_registry(port, Registry::default())
+    }
+
+    /// Serve a registry that other crates have already registered their
+    /// metrics on (e.g. `hydra_ai::AiMetrics`), alongside `HydraMetrics`.
+    pub fn with_registry(port: u16, mut registry: Registry) -> (Self, HydraMetrics) {
         let metrics = HydraMetrics::new(&mut registry);
         (
             Self {
@@ -0,0 +1,652 @@
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use futures::future::join_all;
+use hydra_ai::AuditLog;
+use hydra_core::position::{CompletedTrade, Position};
+use hydra_core::signal::PriceTick;
+use hydra_core::traits::SellExecutor;
+use hydra_monitor::TradeJournal;
+use hydra_risk::{CircuitBreaker, DailyLimits, PositionManager};
+use hydra_strategy::{ExitEvaluator, ExitPlan, SellInstruction};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::sync::mpsc;
+use tracing::{error, info, warn};
+
+use crate::phase::Phase;
+use crate::tracker::PhaseTracker;
+
+/// One position in `Phase::Monitoring`
+struct Monitored {
+    position: Position,
+    evaluator: ExitEvaluator,
+    tracker: PhaseTracker,
+    /// Sum of `fraction * fill price` over the sells so far
+    filled_value_usd: f64,
+    sold_fraction: f64,
+    pnl_sol: f64,
+    /// A sell for this position has been sent and not settled yet
+    selling: bool,
+}
+
+/// A sell decided under the lock and sent after releasing it
+struct PendingSell {
+    id: String,
+    position: Position,
+    /// Evaluator state to restore if the sell fails
+    before: ExitEvaluator,
+    sell: SellInstruction,
+}
+
+/// Watches every open position in the `PositionManager` against its
+/// `ExitPlan`, sells through the executor and books the closed trade.
+///
+/// Positions opened without `track` are picked up on the next tick or
+/// clock check with a single take-profit at `take_profit_pct` and a stop
+/// at `stop_loss_pct`.
+pub struct ExitEngine<E> {
+    executor: E,
+    positions: Arc<PositionManager>,
+    journal: TradeJournal,
+    daily_limits: Arc<Mutex<DailyLimits>>,
+    breaker: Arc<Mutex<CircuitBreaker>>,
+    monitored: tokio::sync::Mutex<HashMap<String, Monitored>>,
+    audit: Option<AuditLog>,
+}
+
+impl<E: SellExecutor> ExitEngine<E> {
+    pub fn new(
+        executor: E,
+        positions: Arc<PositionManager>,
+        journal: TradeJournal,
+        daily_limits: Arc<Mutex<DailyLimits>>,
+        breaker: Arc<Mutex<CircuitBreaker>>,
+    ) -> Self {
+        Self {
+            executor,
+            positions,
+            journal,
+            daily_limits,
+            breaker,
+            monitored: tokio::sync::Mutex::new(HashMap::new()),
+            audit: None,
+        }
+    }
+
+    /// Link each position to the AI scoring record of its mint once it
+    /// is monitored, so audit outcomes join by position id
+    pub fn with_audit_log(mut self, audit: AuditLog) -> Self {
+        self.audit = Some(audit);
+        self
+    }
+
+    fn link_audit(&self, position: &Position) {
+        if let Some(audit) = &self.audit {
+            audit.link_position(&position.mint_address, &position.id);
+        }
+    }
+
+    /// Plan used for positions the engine discovers on its own
+    pub fn default_plan(position: &Position) -> ExitPlan {
+        ExitPlan::new(position.stop_loss_pct).with_tranche(position.take_profit_pct, 1.0)
+    }
+
+    /// Monitor `position` with `plan`, continuing the trade's phase history
+    pub async fn track(
+        &self,
+        position: Position,
+        plan: ExitPlan,
+        mut tracker: PhaseTracker,
+    ) -> Result<()> {
+        plan.validate()?;
+        while tracker.current_phase().number() < Phase::Monitoring.number() {
+            tracker.advance()?;
+        }
+        let evaluator =
+            ExitEvaluator::new(plan, position.entry_price_usd).with_opened_at(position.opened_at);
+        let mut monitored = self.monitored.lock().await;
+        if !monitored.contains_key(&position.id) {
+            self.link_audit(&position);
+        }
+        monitored.insert(
+            position.id.clone(),
+            Monitored {
+                position,
+                evaluator,
+                tracker,
+                filled_value_usd: 0.0,
+                sold_fraction: 0.0,
+                pnl_sol: 0.0,
+                selling: false,
+            },
+        );
+        Ok(())
+    }
+
+    /// Mints with an open position, for the price subscription
+    pub fn mints(&self) -> Vec<String> {
+        let mut mints: Vec<String> = self
+            .positions
+            .all()
+            .into_iter()
+            .map(|p| p.mint_address)
+            .collect();
+        mints.sort();
+        mints.dedup();
+        mints
+    }
+
+    pub async fn phase(&self, position_id: &str) -> Option<Phase> {
+        self.monitored
+            .lock()
+            .await
+            .get(position_id)
+            .map(|m| m.tracker.current_phase().clone())
+    }
+
+    /// Evaluate every position on the tick's mint. Returns the trades
+    /// closed by it.
+    pub async fn on_tick(&self, tick: &PriceTick) -> Vec<CompletedTrade> {
+        self.evaluate(|m| {
+            if m.position.mint_address != tick.mint_address {
+                return None;
+            }
+            m.evaluator.on_tick(tick)
+        })
+        .await
+    }
+
+    /// Check time exits for every position at the last seen prices
+    pub async fn on_clock(&self, now: DateTime<Utc>) -> Vec<CompletedTrade> {
+        self.evaluate(|m| m.evaluator.on_clock(now)).await
+    }
+
+    /// Process ticks until the channel closes, checking time exits every
+    /// `clock_interval` in between
+    pub async fn run(&self, mut ticks: mpsc::Receiver<PriceTick>, clock_interval: Duration) {
+        let mut clock = tokio::time::interval(clock_interval);
+        loop {
+            tokio::select! {
+                tick = ticks.recv() => match tick {
+                    Some(tick) => {
+                        self.on_tick(&tick).await;
+                    }
+                    None => break,
+                },
+                _ = clock.tick() => {
+                    self.on_clock(Utc::now()).await;
+                }
+            }
+        }
+        info!("Exit engine stopped: price feed closed");
+    }
+
+    /// Decide sells under the lock, send them concurrently without it,
+    /// then apply the fills. A position with a sell in flight is not
+    /// evaluated again until that sell settles.
+    async fn evaluate(
+        &self,
+        mut check: impl FnMut(&mut Monitored) -> Option<SellInstruction>,
+    ) -> Vec<CompletedTrade> {
+        let pending: Vec<PendingSell> = {
+            let mut monitored = self.monitored.lock().await;
+            self.sync(&mut monitored);
+            monitored
+                .iter_mut()
+                .filter(|(_, m)| !m.selling)
+                .filter_map(|(id, m)| {
+                    let before = m.evaluator.clone();
+                    let sell = check(m)?;
+                    m.selling = true;
+                    Some(PendingSell {
+                        id: id.clone(),
+                        position: m.position.clone(),
+                        before,
+                        sell,
+                    })
+                })
+                .collect()
+        };
+        if pending.is_empty() {
+            return Vec::new();
+        }
+
+        let fills = join_all(pending.iter().map(|p| {
+            self.executor
+                .sell(&p.position, p.sell.fraction, p.sell.reason.as_str())
+        }))
+        .await;
+
+        let mut closed = Vec::new();
+        let mut monitored = self.monitored.lock().await;
+        for (p, fill) in pending.into_iter().zip(fills) {
+            let Some(m) = monitored.get_mut(&p.id) else {
+                warn!(position_id = %p.id, "Position closed elsewhere during exit sell");
+                continue;
+            };
+            m.selling = false;
+            match fill {
+                Ok(fill_price_usd) => {
+                    m.filled_value_usd += p.sell.fraction * fill_price_usd;
+                    m.sold_fraction += p.sell.fraction;
+                    m.pnl_sol += m.position.size_sol
+                        * p.sell.fraction
+                        * (fill_price_usd / m.position.entry_price_usd - 1.0);
+                    info!(
+                        position_id = %p.id,
+                        fraction = p.sell.fraction,
+                        fill_price_usd,
+                        reason = p.sell.reason.as_str(),
+                        remaining = m.evaluator.remaining(),
+                        "Exit sell filled"
+                    );
+                    if m.evaluator.is_closed() {
+                        if let Some(m) = monitored.remove(&p.id) {
+                            // Before the lock is released, or a concurrent
+                            // sync() would adopt the position again
+                            if let Err(e) = self.positions.close(&p.id) {
+                                warn!(position_id = %p.id, error = %e, "Position already closed");
+                            }
+                            closed.push((m, p.sell.reason.as_str()));
+                        }
+                    }
+                }
+                Err(e) => {
+                    // Undo the evaluator step so the exit is retried next time
+                    m.evaluator = p.before;
+                    warn!(position_id = %p.id, error = %e, "Exit sell failed");
+                }
+            }
+        }
+        drop(monitored);
+
+        let mut trades = Vec::with_capacity(closed.len());
+        for (m, reason) in closed {
+            trades.push(self.close(m, reason).await);
+        }
+        trades
+    }
+
+    /// Pick up positions opened elsewhere and drop ones closed elsewhere
+    fn sync(&self, monitored: &mut HashMap<String, Monitored>) {
+        let open = self.positions.all();
+        monitored.retain(|id, _| open.iter().any(|p| &p.id == id));
+        for position in open {
+            if monitored.contains_key(&position.id) {
+                continue;
+            }
+            let mut tracker = PhaseTracker::new(position.id.clone());
+            while tracker.current_phase() != &Phase::Monitoring {
+                // Cannot fail before TradeClosed
+                let _ = tracker.advance();
+            }
+            let evaluator =
+                ExitEvaluator::new(Self::default_plan(&position), position.entry_price_usd)
+                    .with_opened_at(position.opened_at);
+            self.link_audit(&position);
+            monitored.insert(
+                position.id.clone(),
+                Monitored {
+                    position,
+                    evaluator,
+                    tracker,
+                    filled_value_usd: 0.0,
+                    sold_fraction: 0.0,
+                    pnl_sol: 0.0,
+                    selling: false,
+                },
+            );
+        }
+    }
+
+    /// Book a trade whose position has already left the `PositionManager`
+    async fn close(&self, mut m: Monitored, reason: &str) -> CompletedTrade {
+        let trade = CompletedTrade {
+            position_id: m.position.id.clone(),
+            mint_address: m.position.mint_address.clone(),
+            entry_price_usd: m.position.entry_price_usd,
+            exit_price_usd: m.filled_value_usd / m.sold_fraction,
+            size_sol: m.position.size_sol,
+            pnl_sol: m.pnl_sol,
+            opened_at: m.position.opened_at,
+            closed_at: Utc::now(),
+            exit_reason: reason.to_string(),
+            strategy_id: m.position.strategy_id.clone(),
+        };
+
+        // The journal writes with blocking std::fs
+        let journal = self.journal.clone();
+        let journaled = trade.clone();
+        match tokio::task::spawn_blocking(move || journal.record(&journaled)).await {
+            Ok(Ok(())) => {}
+            Ok(Err(e)) => {
+                error!(position_id = %trade.position_id, error = %e, "Failed to journal trade")
+            }
+            Err(e) => {
+                error!(position_id = %trade.position_id, error = %e, "Journal task failed")
+            }
+        }
+        self.daily_limits
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .record_trade_pnl(trade.pnl_sol);
+        let mut breaker = self.breaker.lock().unwrap_or_else(|e| e.into_inner());
+        if trade.pnl_sol < 0.0 {
+            breaker.record_loss();
+        } else {
+            breaker.record_win();
+        }
+        drop(breaker);
+        if let Err(e) = m.tracker.advance() {
+            warn!(position_id = %trade.position_id, error = %e, "Phase not advanced");
+        }
+
+        info!(
+            position_id = %trade.position_id,
+            pnl_sol = trade.pnl_sol,
+            exit_price_usd = trade.exit_price_usd,
+            reason,
+            "Position closed"
+        );
+        trade
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicBool, Ordering};
+
+    /// Fills every sell at `fill_price_usd` unless `fail` is set
+    struct MockSeller {
+        fill_price_usd: Mutex<f64>,
+        fail: AtomicBool,
+        sells: Mutex<Vec<(String, f64, String)>>,
+        /// Sells on this mint wait for `gate`
+        slow_mint: Mutex<Option<String>>,
+        gate: tokio::sync::Notify,
+    }
+
+    impl MockSeller {
+        fn new() -> Self {
+            Self {
+                fill_price_usd: Mutex::new(0.0),
+                fail: AtomicBool::new(false),
+                sells: Mutex::new(Vec::new()),
+                slow_mint: Mutex::new(None),
+                gate: tokio::sync::Notify::new(),
+            }
+        }
+    }
+
+    impl SellExecutor for &MockSeller {
+        async fn sell(&self, position: &Position, fraction: f64, reason: &str) -> Result<f64> {
+            let slow = self.slow_mint.lock().unwrap().as_deref() == Some(&position.mint_address);
+            if slow {
+                self.gate.notified().await;
+            }
+            if self.fail.load(Ordering::SeqCst) {
+                anyhow::bail!("rpc down");
+            }
+            self.sells
+                .lock()
+                .unwrap()
+                .push((position.id.clone(), fraction, reason.to_string()));
+            Ok(*self.fill_price_usd.lock().unwrap())
+        }
+    }
+
+    fn engine<'a>(
+        seller: &'a MockSeller,
+        name: &str,
+    ) -> (ExitEngine<&'a MockSeller>, Arc<PositionManager>, String) {
+        let path = std::env::temp_dir()
+            .join(format!("hydra_exit_{}_{}.csv", name, std::process::id()))
+            .to_string_lossy()
+            .to_string();
+        let _ = std::fs::remove_file(&path);
+        let positions = Arc::new(PositionManager::new(5));
+        let engine = ExitEngine::new(
+            seller,
+            positions.clone(),
+            TradeJournal::new(path.clone()),
+            Arc::new(Mutex::new(DailyLimits::new(5.0))),
+            Arc::new(Mutex::new(CircuitBreaker::new(1))),
+        );
+        (engine, positions, path)
+    }
+
+    async fn tick(
+        engine: &ExitEngine<&MockSeller>,
+        seller: &MockSeller,
+        price: f64,
+    ) -> Vec<CompletedTrade> {
+        *seller.fill_price_usd.lock().unwrap() = price;
+        engine
+            .on_tick(&PriceTick::new("mint".to_string(), price))
+            .await
+    }
+
+    #[tokio::test]
+    async fn test_stop_loss_books_trade() {
+        let seller = MockSeller::new();
+        let (engine, positions, path) = engine(&seller, "sl");
+        positions
+            .open(Position::new(
+                "p1".into(),
+                "mint".into(),
+                1.0,
+                2.0,
+                0.5,
+                0.2,
+            ))
+            .unwrap();
+
+        assert!(tick(&engine, &seller, 1.1).await.is_empty());
+        assert_eq!(engine.phase("p1").await, Some(Phase::Monitoring));
+
+        let closed = tick(&engine, &seller, 0.75).await;
+        assert_eq!(closed.len(), 1);
+        assert_eq!(closed[0].exit_reason, "stop_loss");
+        assert!((closed[0].pnl_sol + 0.5).abs() < 1e-10);
+        assert_eq!(positions.open_count(), 0);
+        assert_eq!(engine.phase("p1").await, None);
+        assert!(engine.breaker.lock().unwrap().is_tripped());
+        assert!((engine.daily_limits.lock().unwrap().daily_pnl_sol() + 0.5).abs() < 1e-10);
+
+        let journal = TradeJournal::new(path.clone()).load().unwrap();
+        assert_eq!(journal.len(), 1);
+        assert_eq!(journal[0].position_id, "p1");
+        let _ = std::fs::remove_file(path);
+    }
+
+    #[tokio::test]
+    async fn test_scaled_exit_with_custom_plan() {
+        let seller = MockSeller::new();
+        let (engine, positions, path) = engine(&seller, "scaled");
+        let position = Position::new("p1".into(), "mint".into(), 1.0, 1.0, 0.5, 0.2);
+        positions.open(position.clone()).unwrap();
+        let plan = ExitPlan::new(0.2)
+            .with_tranche(0.5, 0.5)
+            .with_breakeven_after_first_tp();
+        engine
+            .track(position, plan, PhaseTracker::new("p1".into()))
+            .await
+            .unwrap();
+
+        assert!(tick(&engine, &seller, 1.5).await.is_empty());
+        let closed = tick(&engine, &seller, 1.0).await;
+        assert_eq!(closed[0].exit_reason, "breakeven_stop");
+        // Half sold at 1.5, half at 1.0
+        assert!((closed[0].exit_price_usd - 1.25).abs() < 1e-10);
+        assert!((closed[0].pnl_sol - 0.25).abs() < 1e-10);
+        assert!(!engine.breaker.lock().unwrap().is_tripped());
+        assert_eq!(seller.sells.lock().unwrap().len(), 2);
+        let _ = std::fs::remove_file(path);
+    }
+
+    #[tokio::test]
+    async fn test_failed_sell_is_retried() {
+        let seller = MockSeller::new();
+        let (engine, positions, path) = engine(&seller, "retry");
+        positions
+            .open(Position::new(
+                "p1".into(),
+                "mint".into(),
+                1.0,
+                1.0,
+                0.5,
+                0.2,
+            ))
+            .unwrap();
+
+        seller.fail.store(true, Ordering::SeqCst);
+        assert!(tick(&engine, &seller, 0.7).await.is_empty());
+        assert_eq!(positions.open_count(), 1);
+
+        seller.fail.store(false, Ordering::SeqCst);
+        let closed = tick(&engine, &seller, 0.7).await;
+        assert_eq!(closed.len(), 1);
+        assert_eq!(positions.open_count(), 0);
+        let _ = std::fs::remove_file(path);
+    }
+
+    #[tokio::test]
+    async fn test_monitored_positions_link_audit_records() {
+        let seller = MockSeller::new();
+        let (engine, positions, path) = engine(&seller, "audit");
+        let audit_path =
+            std::env::temp_dir().join(format!("hydra_exit_audit_{}.jsonl", std::process::id()));
+        let _ = std::fs::remove_file(&audit_path);
+        let (audit, handle) = AuditLog::spawn(&audit_path);
+        audit.record(hydra_ai::AuditRecord {
+            timestamp: Utc::now(),
+            mint_address: "mint".to_string(),
+            position_id: None,
+            prompt_version: "v3".to_string(),
+            model: "test".to_string(),
+            prompt: String::new(),
+            raw_content: None,
+            score: None,
+            latency_ms: 0,
+            error: None,
+        });
+        let engine = engine.with_audit_log(audit);
+        positions
+            .open(Position::new(
+                "p1".into(),
+                "mint".into(),
+                1.0,
+                1.0,
+                0.5,
+                0.2,
+            ))
+            .unwrap();
+        tick(&engine, &seller, 1.0).await;
+        tick(&engine, &seller, 1.1).await;
+        drop(engine);
+        handle.await.unwrap();
+
+        let records = hydra_ai::load_audit_log(&audit_path).unwrap();
+        assert_eq!(records[0].position_id.as_deref(), Some("p1"));
+        let _ = std::fs::remove_file(audit_path);
+        let _ = std::fs::remove_file(path);
+    }
+
+    #[tokio::test]
+    async fn test_slow_sell_does_not_block_other_positions() {
+        let seller = MockSeller::new();
+        let (engine, positions, path) = engine(&seller, "concurrent");
+        for (id, mint) in [("p1", "slow"), ("p2", "fast")] {
+            positions
+                .open(Position::new(id.into(), mint.into(), 1.0, 1.0, 0.5, 0.2))
+                .unwrap();
+        }
+        *seller.slow_mint.lock().unwrap() = Some("slow".to_string());
+        *seller.fill_price_usd.lock().unwrap() = 0.7;
+
+        let slow_tick = PriceTick::new("slow".to_string(), 0.7);
+        let slow = engine.on_tick(&slow_tick);
+        let fast = async {
+            let closed = engine
+                .on_tick(&PriceTick::new("fast".to_string(), 0.7))
+                .await;
+            // The slow sell is still in flight, and not sent twice
+            assert!(engine
+                .on_tick(&PriceTick::new("slow".to_string(), 0.6))
+                .await
+                .is_empty());
+            seller.gate.notify_one();
+            closed
+        };
+        let (slow, fast) =
+            tokio::time::timeout(Duration::from_secs(5), async { tokio::join!(slow, fast) })
+                .await
+                .expect("stop-loss on one mint waited for a sell on another");
+
+        assert_eq!(fast[0].position_id, "p2");
+        assert_eq!(slow[0].position_id, "p1");
+        assert_eq!(seller.sells.lock().unwrap().len(), 2);
+        assert_eq!(positions.open_count(), 0);
+        let _ = std::fs::remove_file(path);
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_tick_during_slow_journal_write_does_not_sell_again() {
+        let seller = MockSeller::new();
+        let (engine, positions, path) = engine(&seller, "slow_journal");
+        // Appending to a FIFO blocks until a reader opens it
+        let status = std::process::Command::new("mkfifo")
+            .arg(&path)
+            .status()
+            .unwrap();
+        assert!(status.success());
+        positions
+            .open(Position::new(
+                "p1".into(),
+                "mint".into(),
+                1.0,
+                1.0,
+                0.5,
+                0.2,
+            ))
+            .unwrap();
+
+        let first = tick(&engine, &seller, 0.7);
+        let second = async {
+            while seller.sells.lock().unwrap().is_empty() {
+                tokio::task::yield_now().await;
+            }
+            tokio::time::sleep(Duration::from_millis(50)).await;
+            // A second sell would block on its own journal write
+            let again =
+                tokio::time::timeout(Duration::from_secs(1), tick(&engine, &seller, 0.7)).await;
+            // Drain every write from a plain thread, so no journal write
+            // outlives the runtime
+            let (tx, rx) = std::sync::mpsc::channel();
+            let reader = path.clone();
+            std::thread::spawn(move || {
+                while let Ok(rows) = std::fs::read_to_string(&reader) {
+                    if tx.send(rows).is_err() {
+                        break;
+                    }
+                }
+            });
+            (again, rx)
+        };
+        let (closed, (again, rx)) = tokio::time::timeout(Duration::from_secs(5), async {
+            tokio::join!(first, second)
+        })
+        .await
+        .unwrap();
+        let journal = rx.recv_timeout(Duration::from_secs(5)).unwrap();
+
+        assert!(again.is_ok_and(|closed| closed.is_empty()));
+        assert_eq!(closed.len(), 1);
+        assert_eq!(seller.sells.lock().unwrap().len(), 1);
+        assert!(journal.contains("p1"));
+        let _ = std::fs::remove_file(path);
+    }
+}
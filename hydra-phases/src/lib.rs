@@ -1,5 +1,7 @@
+pub mod engine;
 pub mod phase;
 pub mod tracker;
 
+pub use engine::ExitEngine;
 pub use phase::Phase;
 pub use tracker::PhaseTracker;
@@ -57,6 +57,13 @@ impl DailyLimits {
         Ok(())
     }
 
+    /// How much more can be lost today before `check` fails.
+    /// Profits made earlier in the day extend the budget.
+    pub fn remaining_loss_budget_sol(&mut self) -> f64 {
+        self.maybe_reset();
+        (self.max_daily_loss_sol + self.realized_pnl_sol).max(0.0)
+    }
+
     pub fn daily_pnl_sol(&mut self) -> f64 {
         self.maybe_reset();
         self.realized_pnl_sol
@@ -91,4 +98,13 @@ mod tests {
         dl.record_trade_pnl(10.0);
         assert!(dl.check().is_ok());
     }
+
+    #[test]
+    fn test_remaining_loss_budget() {
+        let mut dl = DailyLimits::new(2.0);
+        dl.record_trade_pnl(-0.5);
+        assert!((dl.remaining_loss_budget_sol() - 1.5).abs() < 1e-10);
+        dl.record_trade_pnl(-3.0);
+        assert_eq!(dl.remaining_loss_budget_sol(), 0.0);
+    }
 }
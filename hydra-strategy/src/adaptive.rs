@@ -0,0 +1,362 @@
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use hydra_core::signal::MintSignal;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::Mutex;
+use std::time::Duration;
+use tracing::info;
+
+use crate::filters::{Decision, Filter};
+
+/// Signal feature tracked by the adaptive filter
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Feature {
+    Volume,
+    MarketCap,
+    Liquidity,
+    HolderCount,
+    /// Holders gained per minute, from `MintSignal::flow` like `BuysPerSec`
+    HolderGrowth,
+    /// From `MintSignal::flow`, skipped when the signal has none
+    BuysPerSec,
+}
+
+impl Feature {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Volume => "volume",
+            Self::MarketCap => "market_cap",
+            Self::Liquidity => "liquidity",
+            Self::HolderCount => "holder_count",
+            Self::HolderGrowth => "holder_growth",
+            Self::BuysPerSec => "buys_per_sec",
+        }
+    }
+}
+
+/// How the running mean and variance are updated
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Estimator {
+    /// Exact mean and variance over every sample ever seen
+    Welford,
+    /// Exponentially weighted, older samples lose half their weight
+    /// every `half_life`
+    Ewma { half_life: Duration },
+}
+
+/// Online mean and variance of one feature
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct RollingStats {
+    pub count: u64,
+    pub mean: f64,
+    /// Sum of squared deviations for Welford, variance for EWMA
+    m2: f64,
+    pub last_update: Option<DateTime<Utc>>,
+}
+
+impl RollingStats {
+    pub fn update(&mut self, estimator: Estimator, value: f64, at: DateTime<Utc>) {
+        self.count += 1;
+        match estimator {
+            Estimator::Welford => {
+                let delta = value - self.mean;
+                self.mean += delta / self.count as f64;
+                self.m2 += delta * (value - self.mean);
+            }
+            Estimator::Ewma { half_life } => {
+                if self.count == 1 {
+                    self.mean = value;
+                    self.m2 = 0.0;
+                } else {
+                    let elapsed = self
+                        .last_update
+                        .map(|last| (at - last).to_std().unwrap_or_default())
+                        .unwrap_or_default();
+                    // Never weight a sample below 1/n, so bursts with identical
+                    // timestamps and the first samples still move the estimate
+                    let decay = if half_life.is_zero() {
+                        1.0
+                    } else {
+                        1.0 - 0.5f64.powf(elapsed.as_secs_f64() / half_life.as_secs_f64())
+                    };
+                    let alpha = decay.max(1.0 / self.count as f64);
+                    let diff = value - self.mean;
+                    let incr = alpha * diff;
+                    self.mean += incr;
+                    self.m2 = (1.0 - alpha) * (self.m2 + diff * incr);
+                }
+            }
+        }
+        self.last_update = Some(at);
+    }
+
+    pub fn variance(&self, estimator: Estimator) -> f64 {
+        match estimator {
+            Estimator::Welford if self.count > 1 => self.m2 / (self.count - 1) as f64,
+            Estimator::Welford => 0.0,
+            Estimator::Ewma { .. } => self.m2,
+        }
+    }
+
+    pub fn std_dev(&self, estimator: Estimator) -> f64 {
+        self.variance(estimator).max(0.0).sqrt()
+    }
+
+    pub fn z_score(&self, estimator: Estimator, value: f64) -> f64 {
+        let std_dev = self.std_dev(estimator);
+        if std_dev == 0.0 {
+            return 0.0;
+        }
+        (value - self.mean) / std_dev
+    }
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct AdaptiveState {
+    stats: HashMap<Feature, RollingStats>,
+}
+
+/// `ZScoreFilter` whose mean and variance are learned online from every
+/// signal it sees. Each signal is judged against the statistics as they
+/// were before it arrived, then folded into them. Until a feature has
+/// `warmup` samples, signals are rejected with reason `warming_up`.
+pub struct AdaptiveZScoreFilter {
+    features: Vec<(Feature, f64)>,
+    estimator: Estimator,
+    warmup: u64,
+    state: Mutex<AdaptiveState>,
+}
+
+impl AdaptiveZScoreFilter {
+    pub fn new(estimator: Estimator, warmup: u64) -> Self {
+        Self {
+            features: Vec::new(),
+            estimator,
+            warmup,
+            state: Mutex::new(AdaptiveState::default()),
+        }
+    }
+
+    /// Require the z-score of `feature` to be at least `threshold`
+    pub fn with_feature(mut self, feature: Feature, threshold: f64) -> Self {
+        self.features.push((feature, threshold));
+        self
+    }
+
+    pub fn stats(&self, feature: Feature) -> Option<RollingStats> {
+        self.lock().stats.get(&feature).cloned()
+    }
+
+    pub fn is_warm(&self) -> bool {
+        let state = self.lock();
+        self.features.iter().all(|(feature, _)| {
+            state
+                .stats
+                .get(feature)
+                .is_some_and(|s| s.count >= self.warmup)
+        })
+    }
+
+    /// Write the learned statistics so a restart does not need a new warm-up
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<()> {
+        let path = path.as_ref();
+        let json = serde_json::to_vec_pretty(&*self.lock())
+            .context("Failed to serialize adaptive filter state")?;
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let tmp = path.with_extension("tmp");
+        std::fs::write(&tmp, json).with_context(|| format!("Failed to write {}", tmp.display()))?;
+        std::fs::rename(&tmp, path)
+            .with_context(|| format!("Failed to move state into {}", path.display()))?;
+        Ok(())
+    }
+
+    /// Restore statistics written by `save`. A missing file is not an error.
+    pub fn load(&self, path: impl AsRef<Path>) -> Result<bool> {
+        let path = path.as_ref();
+        if !path.exists() {
+            return Ok(false);
+        }
+        let json =
+            std::fs::read(path).with_context(|| format!("Failed to read {}", path.display()))?;
+        let restored: AdaptiveState =
+            serde_json::from_slice(&json).context("Failed to deserialize adaptive filter state")?;
+        info!(
+            features = restored.stats.len(),
+            path = %path.display(),
+            "Adaptive filter state restored"
+        );
+        self.lock().stats = restored.stats;
+        Ok(true)
+    }
+
+    fn feature_value(feature: Feature, signal: &MintSignal) -> Option<f64> {
+        match feature {
+            Feature::Volume => Some(signal.volume_24h_usd),
+            Feature::MarketCap => Some(signal.market_cap_usd),
+            Feature::Liquidity => Some(signal.liquidity_usd),
+            Feature::HolderCount => Some(signal.holder_count as f64),
+            Feature::HolderGrowth => signal.flow.map(|f| f.holder_growth_per_min),
+            Feature::BuysPerSec => signal.flow.map(|f| f.buys_per_sec),
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, AdaptiveState> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl Filter for AdaptiveZScoreFilter {
+    fn name(&self) -> &str {
+        "adaptive_zscore"
+    }
+
+    fn evaluate(&self, signal: &MintSignal) -> Decision {
+        let mut state = self.lock();
+        let mut decision = Decision::Pass;
+
+        for &(feature, threshold) in &self.features {
+            // A non-finite value would poison the running stats for good
+            let Some(value) = Self::feature_value(feature, signal).filter(|v| v.is_finite()) else {
+                continue;
+            };
+            let stats = state.stats.entry(feature).or_default();
+            if decision.is_pass() {
+                if stats.count < self.warmup {
+                    decision = Decision::reject(
+                        self.name(),
+                        "warming_up",
+                        stats.count as f64,
+                        self.warmup as f64,
+                    );
+                } else {
+                    let z = stats.z_score(self.estimator, value);
+                    if !z.is_finite() || z < threshold {
+                        decision = Decision::reject(
+                            self.name(),
+                            &format!("{}_z_below", feature.as_str()),
+                            z,
+                            threshold,
+                        );
+                    }
+                }
+            }
+            stats.update(self.estimator, value, signal.timestamp);
+        }
+        decision
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn signal(mint: &str, volume: f64, holders: u64, at: DateTime<Utc>) -> MintSignal {
+        let mut s = MintSignal::new(mint.to_string(), 0.0, volume, 0.0, holders, 0.0, 0.0);
+        s.timestamp = at;
+        s
+    }
+
+    #[test]
+    fn test_welford_matches_batch_statistics() {
+        let mut stats = RollingStats::default();
+        let values = [2.0, 4.0, 4.0, 4.0, 5.0, 5.0, 7.0, 9.0];
+        for v in values {
+            stats.update(Estimator::Welford, v, Utc::now());
+        }
+        assert!((stats.mean - 5.0).abs() < 1e-12);
+        // sample variance of the set is 32 / 7
+        assert!((stats.variance(Estimator::Welford) - 32.0 / 7.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_ewma_forgets_old_regime() {
+        let estimator = Estimator::Ewma {
+            half_life: Duration::from_secs(60),
+        };
+        let start = Utc::now();
+        let mut stats = RollingStats::default();
+        for i in 0..50 {
+            stats.update(estimator, 100.0, start + chrono::Duration::seconds(i));
+        }
+        for i in 0..20 {
+            let at = start + chrono::Duration::seconds(50 + i * 60);
+            stats.update(estimator, 1_000.0, at);
+        }
+        assert!(stats.mean > 990.0, "mean {}", stats.mean);
+    }
+
+    #[test]
+    fn test_non_finite_values_are_skipped() {
+        let filter =
+            AdaptiveZScoreFilter::new(Estimator::Welford, 2).with_feature(Feature::Volume, 1.0);
+        let now = Utc::now();
+        for v in [100.0, f64::NAN, 110.0, f64::INFINITY] {
+            filter.evaluate(&signal("m", v, 0, now));
+        }
+        let stats = filter.stats(Feature::Volume).unwrap();
+        assert_eq!(stats.count, 2);
+        assert!((stats.mean - 105.0).abs() < 1e-12);
+        assert!(!filter.evaluate(&signal("m", 100.0, 0, now)).is_pass());
+        assert!(filter.evaluate(&signal("m", 500.0, 0, now)).is_pass());
+    }
+
+    #[test]
+    fn test_warmup_then_judges_against_prior_stats() {
+        let filter =
+            AdaptiveZScoreFilter::new(Estimator::Welford, 5).with_feature(Feature::Volume, 1.0);
+        let now = Utc::now();
+        for (i, v) in [100.0, 110.0, 90.0, 105.0, 95.0].into_iter().enumerate() {
+            let decision = filter.evaluate(&signal(&format!("m{i}"), v, 0, now));
+            assert!(
+                matches!(decision, Decision::Reject { ref reason, .. } if reason == "warming_up")
+            );
+        }
+        assert!(filter.is_warm());
+        assert!(filter.evaluate(&signal("hot", 200.0, 0, now)).is_pass());
+        assert!(!filter.evaluate(&signal("cold", 100.0, 0, now)).is_pass());
+    }
+
+    #[test]
+    fn test_holder_growth_comes_from_flow_features() {
+        let filter = AdaptiveZScoreFilter::new(Estimator::Welford, 0)
+            .with_feature(Feature::HolderGrowth, 0.0);
+        let tracker = crate::flow::FlowTracker::new(Duration::from_secs(60));
+        let now = Utc::now();
+        filter.evaluate(&signal("m", 0.0, 10, now));
+        assert!(filter.stats(Feature::HolderGrowth).is_none());
+
+        let mut first = signal("m", 0.0, 10, now);
+        tracker.enrich(&mut first);
+        let mut second = signal("m", 0.0, 40, now + chrono::Duration::seconds(30));
+        tracker.enrich(&mut second);
+        filter.evaluate(&second);
+        let stats = filter.stats(Feature::HolderGrowth).unwrap();
+        assert!((stats.mean - 60.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_state_survives_restart() {
+        let path = std::env::temp_dir().join(format!("hydra_adaptive_{}.json", std::process::id()));
+        let filter =
+            AdaptiveZScoreFilter::new(Estimator::Welford, 3).with_feature(Feature::Volume, 0.0);
+        for v in [1.0, 2.0, 3.0] {
+            filter.evaluate(&signal("m", v, 0, Utc::now()));
+        }
+        filter.save(&path).unwrap();
+
+        let restored =
+            AdaptiveZScoreFilter::new(Estimator::Welford, 3).with_feature(Feature::Volume, 0.0);
+        assert!(restored.load(&path).unwrap());
+        assert!(restored.is_warm());
+        assert_eq!(
+            restored.stats(Feature::Volume),
+            filter.stats(Feature::Volume)
+        );
+        std::fs::remove_file(&path).ok();
+    }
+}
@@ -0,0 +1,166 @@
+use hydra_core::signal::MintSignal;
+use tracing::{debug, info};
+
+use crate::filters::{Decision, Filter};
+use crate::metrics::{FilterMetrics, RejectionLabels};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ChainMode {
+    /// Stop at the first rejection
+    #[default]
+    ShortCircuit,
+    /// Run every filter so each rejecting rule is counted
+    EvaluateAll,
+}
+
+/// Result of running a signal through a `FilterChain`
+#[derive(Debug, Clone, Default)]
+pub struct ChainOutcome {
+    pub evaluated: usize,
+    pub rejections: Vec<Decision>,
+}
+
+impl ChainOutcome {
+    pub fn passed(&self) -> bool {
+        self.rejections.is_empty()
+    }
+}
+
+/// Ordered set of filters evaluated against each signal
+pub struct FilterChain {
+    filters: Vec<Box<dyn Filter>>,
+    mode: ChainMode,
+    metrics: Option<FilterMetrics>,
+}
+
+impl FilterChain {
+    pub fn new(mode: ChainMode) -> Self {
+        Self {
+            filters: Vec::new(),
+            mode,
+            metrics: None,
+        }
+    }
+
+    pub fn with_filter(mut self, filter: impl Filter + 'static) -> Self {
+        self.filters.push(Box::new(filter));
+        self
+    }
+
+    pub fn with_metrics(mut self, metrics: FilterMetrics) -> Self {
+        self.metrics = Some(metrics);
+        self
+    }
+
+    pub fn len(&self) -> usize {
+        self.filters.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.filters.is_empty()
+    }
+
+    pub fn evaluate(&self, signal: &MintSignal) -> ChainOutcome {
+        let mut outcome = ChainOutcome::default();
+        for filter in &self.filters {
+            outcome.evaluated += 1;
+            let decision = filter.evaluate(signal);
+            if decision.is_pass() {
+                continue;
+            }
+            outcome.rejections.push(decision);
+            if self.mode == ChainMode::ShortCircuit {
+                break;
+            }
+        }
+        self.observe(signal, &outcome);
+        outcome
+    }
+
+    pub fn passes(&self, signal: &MintSignal) -> bool {
+        self.evaluate(signal).passed()
+    }
+
+    fn observe(&self, signal: &MintSignal, outcome: &ChainOutcome) {
+        for rejection in &outcome.rejections {
+            if let Decision::Reject {
+                filter,
+                reason,
+                value,
+                threshold,
+            } = rejection
+            {
+                info!(
+                    mint = %signal.mint_address,
+                    filter = %filter,
+                    reason = %reason,
+                    value,
+                    threshold,
+                    "Filter rejected"
+                );
+                if let Some(metrics) = &self.metrics {
+                    metrics
+                        .rejections
+                        .get_or_create(&RejectionLabels {
+                            filter: filter.clone(),
+                            reason: reason.clone(),
+                        })
+                        .inc();
+                }
+            }
+        }
+        if outcome.passed() {
+            debug!(mint = %signal.mint_address, "Signal passed filter chain");
+        }
+        if let Some(metrics) = &self.metrics {
+            metrics.signals_evaluated.inc();
+            if outcome.passed() {
+                metrics.signals_passed.inc();
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::filters::{McapFilter, RugCheckFilter};
+    use prometheus_client::registry::Registry;
+
+    fn signal(mcap: f64, liquidity: f64) -> MintSignal {
+        MintSignal::new("m".to_string(), mcap, 0.0, 0.0, 200, liquidity, 10.0)
+    }
+
+    fn chain(mode: ChainMode, metrics: FilterMetrics) -> FilterChain {
+        FilterChain::new(mode)
+            .with_filter(McapFilter::new(1_000.0, 100_000.0))
+            .with_filter(RugCheckFilter::new(5_000.0, 30.0, 100))
+            .with_metrics(metrics)
+    }
+
+    #[test]
+    fn test_short_circuit_stops_at_first_rejection() {
+        let metrics = FilterMetrics::new(&mut Registry::default());
+        let chain = chain(ChainMode::ShortCircuit, metrics.clone());
+        let outcome = chain.evaluate(&signal(500.0, 100.0));
+        assert!(!outcome.passed());
+        assert_eq!(outcome.evaluated, 1);
+        assert_eq!(outcome.rejections.len(), 1);
+        assert_eq!(metrics.rejection_count("mcap", "below_min"), 1);
+        assert_eq!(metrics.rejection_count("rug_check", "low_liquidity"), 0);
+    }
+
+    #[test]
+    fn test_evaluate_all_counts_every_rejection() {
+        let metrics = FilterMetrics::new(&mut Registry::default());
+        let chain = chain(ChainMode::EvaluateAll, metrics.clone());
+        let outcome = chain.evaluate(&signal(500.0, 100.0));
+        assert_eq!(outcome.evaluated, 2);
+        assert_eq!(outcome.rejections.len(), 2);
+        assert_eq!(metrics.rejection_count("rug_check", "low_liquidity"), 1);
+
+        assert!(chain.passes(&signal(50_000.0, 10_000.0)));
+        assert_eq!(metrics.signals_evaluated.get(), 2);
+        assert_eq!(metrics.signals_passed.get(), 1);
+    }
+}
@@ -0,0 +1,34 @@
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum StrategyError {
+    #[error("Rule '{rule}': parse error at column {column}: {message}")]
+    RuleParse {
+        rule: String,
+        column: usize,
+        message: String,
+    },
+
+    #[error("Rule '{rule}': unknown field '{field}'")]
+    UnknownField { rule: String, field: String },
+
+    #[error("Rule '{rule}': {message}")]
+    RuleType { rule: String, message: String },
+
+    #[error("Invalid exit plan: {reason}")]
+    InvalidExitPlan { reason: String },
+
+    #[error("Position size {size_sol:.4} SOL below minimum {min_sol:.4} SOL ({limited_by})")]
+    PositionTooSmall {
+        size_sol: f64,
+        min_sol: f64,
+        limited_by: String,
+    },
+
+    #[error("Buying and exiting {tokens:.0} tokens would cost {impact_bps:.0} bps of price impact (max {max_bps} bps)")]
+    ExitInfeasible {
+        tokens: f64,
+        impact_bps: f64,
+        max_bps: u32,
+    },
+}
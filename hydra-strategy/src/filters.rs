@@ -2,6 +2,59 @@ use anyhow::Result;
 use hydra_core::signal::MintSignal;
 use tracing::info;
 
+/// Outcome of running one filter against a signal
+#[derive(Debug, Clone, PartialEq)]
+pub enum Decision {
+    Pass,
+    Reject {
+        filter: String,
+        reason: String,
+        value: f64,
+        threshold: f64,
+    },
+}
+
+impl Decision {
+    pub fn reject(filter: &str, reason: &str, value: f64, threshold: f64) -> Self {
+        Self::Reject {
+            filter: filter.to_string(),
+            reason: reason.to_string(),
+            value,
+            threshold,
+        }
+    }
+
+    pub fn is_pass(&self) -> bool {
+        matches!(self, Self::Pass)
+    }
+
+    fn log(&self, signal: &MintSignal) {
+        if let Self::Reject {
+            filter,
+            reason,
+            value,
+            threshold,
+        } = self
+        {
+            info!(
+                mint = %signal.mint_address,
+                filter = %filter,
+                reason = %reason,
+                value,
+                threshold,
+                "Filter rejected"
+            );
+        }
+    }
+}
+
+/// A pre-trade check. `reason` in a rejection is a stable, low-cardinality
+/// identifier so it can be used as a metric label.
+pub trait Filter: Send + Sync {
+    fn name(&self) -> &str;
+    fn evaluate(&self, signal: &MintSignal) -> Decision;
+}
+
 pub struct McapFilter {
     pub min_usd: f64,
     pub max_usd: f64,
@@ -13,15 +66,38 @@ impl McapFilter {
     }
 
     pub fn passes(&self, signal: &MintSignal) -> bool {
-        let passes = signal.market_cap_usd >= self.min_usd && signal.market_cap_usd <= self.max_usd;
-        if !passes {
-            info!(
-                mint = %signal.mint_address,
-                mcap = signal.market_cap_usd,
-                "McapFilter rejected"
+        let decision = self.evaluate(signal);
+        decision.log(signal);
+        decision.is_pass()
+    }
+}
+
+impl Filter for McapFilter {
+    fn name(&self) -> &str {
+        "mcap"
+    }
+
+    fn evaluate(&self, signal: &MintSignal) -> Decision {
+        if signal.market_cap_usd.is_nan() {
+            return Decision::reject(self.name(), "invalid", f64::NAN, self.min_usd);
+        }
+        if signal.market_cap_usd < self.min_usd {
+            return Decision::reject(
+                self.name(),
+                "below_min",
+                signal.market_cap_usd,
+                self.min_usd,
             );
         }
-        passes
+        if signal.market_cap_usd > self.max_usd {
+            return Decision::reject(
+                self.name(),
+                "above_max",
+                signal.market_cap_usd,
+                self.max_usd,
+            );
+        }
+        Decision::Pass
     }
 }
 
@@ -48,17 +124,23 @@ impl ZScoreFilter {
     }
 
     pub fn passes(&self, signal: &MintSignal) -> bool {
+        let decision = self.evaluate(signal);
+        decision.log(signal);
+        decision.is_pass()
+    }
+}
+
+impl Filter for ZScoreFilter {
+    fn name(&self) -> &str {
+        "zscore"
+    }
+
+    fn evaluate(&self, signal: &MintSignal) -> Decision {
         let z = self.z_score(signal.volume_24h_usd);
-        let passes = z >= self.threshold;
-        if !passes {
-            info!(
-                mint = %signal.mint_address,
-                z_score = z,
-                threshold = self.threshold,
-                "ZScoreFilter rejected"
-            );
+        if z.is_nan() || z < self.threshold {
+            return Decision::reject(self.name(), "volume_z_below", z, self.threshold);
         }
-        passes
+        Decision::Pass
     }
 }
 
@@ -78,31 +160,110 @@ impl RugCheckFilter {
     }
 
     pub fn passes(&self, signal: &MintSignal) -> Result<bool> {
+        let decision = self.evaluate(signal);
+        decision.log(signal);
+        Ok(decision.is_pass())
+    }
+}
+
+impl Filter for RugCheckFilter {
+    fn name(&self) -> &str {
+        "rug_check"
+    }
+
+    fn evaluate(&self, signal: &MintSignal) -> Decision {
         if signal.liquidity_usd < self.min_liquidity_usd {
-            info!(
-                mint = %signal.mint_address,
-                liquidity = signal.liquidity_usd,
-                "RugCheckFilter: insufficient liquidity"
+            return Decision::reject(
+                self.name(),
+                "low_liquidity",
+                signal.liquidity_usd,
+                self.min_liquidity_usd,
             );
-            return Ok(false);
         }
         if signal.top_holder_pct > self.max_top_holder_pct {
-            info!(
-                mint = %signal.mint_address,
-                top_holder_pct = signal.top_holder_pct,
-                "RugCheckFilter: top holder concentration too high"
+            return Decision::reject(
+                self.name(),
+                "top_holder_concentration",
+                signal.top_holder_pct,
+                self.max_top_holder_pct,
             );
-            return Ok(false);
         }
         if signal.holder_count < self.min_holder_count {
-            info!(
-                mint = %signal.mint_address,
-                holder_count = signal.holder_count,
-                "RugCheckFilter: too few holders"
+            return Decision::reject(
+                self.name(),
+                "few_holders",
+                signal.holder_count as f64,
+                self.min_holder_count as f64,
+            );
+        }
+        Decision::Pass
+    }
+}
+
+/// Rejects signals whose off-chain metadata looks low effort or scammy.
+/// Signals without resolved metadata pass unless `require_metadata` is set.
+pub struct MetadataFilter {
+    pub require_metadata: bool,
+    pub min_socials: usize,
+    pub blocked_terms: Vec<String>,
+}
+
+impl MetadataFilter {
+    pub fn new(require_metadata: bool, min_socials: usize, blocked_terms: Vec<String>) -> Self {
+        Self {
+            require_metadata,
+            min_socials,
+            blocked_terms: blocked_terms
+                .into_iter()
+                .map(|t| t.to_lowercase())
+                .collect(),
+        }
+    }
+
+    pub fn passes(&self, signal: &MintSignal) -> bool {
+        let decision = self.evaluate(signal);
+        decision.log(signal);
+        decision.is_pass()
+    }
+}
+
+impl Filter for MetadataFilter {
+    fn name(&self) -> &str {
+        "metadata"
+    }
+
+    fn evaluate(&self, signal: &MintSignal) -> Decision {
+        let Some(metadata) = &signal.metadata else {
+            if self.require_metadata {
+                return Decision::reject(self.name(), "missing", 0.0, 1.0);
+            }
+            return Decision::Pass;
+        };
+        let socials = metadata.social_count();
+        if socials < self.min_socials {
+            return Decision::reject(
+                self.name(),
+                "few_socials",
+                socials as f64,
+                self.min_socials as f64,
             );
-            return Ok(false);
         }
-        Ok(true)
+        let text = format!(
+            "{} {} {}",
+            metadata.name,
+            metadata.symbol,
+            metadata.description.as_deref().unwrap_or("")
+        )
+        .to_lowercase();
+        let blocked = self
+            .blocked_terms
+            .iter()
+            .filter(|t| text.contains(t.as_str()))
+            .count();
+        if blocked > 0 {
+            return Decision::reject(self.name(), "blocked_term", blocked as f64, 0.0);
+        }
+        Decision::Pass
     }
 }
 
@@ -146,6 +307,14 @@ mod tests {
         assert!(!f.passes(&make_signal(200_000.0, 0.0, 0.0, 0.0, 0)));
     }
 
+    #[test]
+    fn test_nan_inputs_are_rejected() {
+        let mcap = McapFilter::new(1_000.0, 100_000.0);
+        assert!(!mcap.passes(&make_signal(f64::NAN, 0.0, 0.0, 0.0, 0)));
+        let z = ZScoreFilter::new(1_000.0, 500.0, 1.0);
+        assert!(!z.passes(&make_signal(0.0, f64::NAN, 0.0, 0.0, 0)));
+    }
+
     #[test]
     fn test_zscore_filter_passes() {
         // mean=1000, std=500, threshold=1.0 → z=(1600-1000)/500=1.2 ≥ 1.0
@@ -193,4 +362,31 @@ mod tests {
         let s = make_signal(0.0, 0.0, 5_000.0, 20.0, 10);
         assert!(!f.passes(&s).unwrap());
     }
+
+    #[test]
+    fn test_metadata_filter() {
+        use hydra_core::signal::TokenMetadata;
+        let f = MetadataFilter::new(true, 1, vec!["RUG".to_string()]);
+        let mut s = make_signal(0.0, 0.0, 0.0, 0.0, 0);
+        assert!(!f.passes(&s));
+        s.metadata = Some(TokenMetadata {
+            name: "Hydra".to_string(),
+            twitter: Some("https://x.com/hydra".to_string()),
+            ..TokenMetadata::default()
+        });
+        assert!(f.passes(&s));
+        s.metadata.as_mut().unwrap().description = Some("not a rug".to_string());
+        assert!(!f.passes(&s));
+        assert!(MetadataFilter::new(false, 1, vec![]).passes(&make_signal(0.0, 0.0, 0.0, 0.0, 0)));
+    }
+
+    #[test]
+    fn test_rejection_carries_value_and_threshold() {
+        let f = RugCheckFilter::new(5_000.0, 30.0, 100);
+        let decision = f.evaluate(&make_signal(0.0, 0.0, 10_000.0, 45.0, 200));
+        assert_eq!(
+            decision,
+            Decision::reject("rug_check", "top_holder_concentration", 45.0, 30.0)
+        );
+    }
 }
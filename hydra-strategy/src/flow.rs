@@ -0,0 +1,215 @@
+use chrono::{DateTime, Utc};
+use hydra_core::signal::{FlowFeatures, MintSignal, TradeEvent, TradeKind, MAX_BUY_SELL_RATIO};
+use std::collections::{HashSet, VecDeque};
+use std::sync::Mutex;
+use std::time::Duration;
+
+use crate::mint_table::MintTable;
+
+#[derive(Debug, Default)]
+struct MintFlow {
+    trades: VecDeque<TradeEvent>,
+    holders_seen: Option<(u64, DateTime<Utc>)>,
+    holder_growth_per_min: f64,
+}
+
+/// Keeps the last `window` of trades per mint and derives `FlowFeatures`
+/// from them, for tokens too young for 24h volume to mean anything
+pub struct FlowTracker {
+    window: Duration,
+    mints: Mutex<MintTable<MintFlow>>,
+}
+
+impl FlowTracker {
+    pub fn new(window: Duration) -> Self {
+        Self {
+            window,
+            mints: Mutex::new(MintTable::default()),
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, MintTable<MintFlow>> {
+        self.mints.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn window_start(&self, now: DateTime<Utc>) -> DateTime<Utc> {
+        now - chrono::Duration::from_std(self.window).unwrap_or_default()
+    }
+
+    pub fn observe(&self, event: &TradeEvent) {
+        let mut mints = self.lock();
+        let start = self.window_start(event.timestamp);
+        let flow = mints.entry(&event.mint_address);
+        flow.trades.push_back(event.clone());
+        while flow.trades.front().is_some_and(|t| t.timestamp < start) {
+            flow.trades.pop_front();
+        }
+    }
+
+    /// Features over the window ending at `now`. `None` for unseen mints.
+    pub fn features(&self, mint_address: &str, now: DateTime<Utc>) -> Option<FlowFeatures> {
+        let mints = self.lock();
+        let flow = mints.get(mint_address)?;
+        Some(self.compute(flow, now))
+    }
+
+    /// Update holder growth from the signal and attach its flow features.
+    /// This is the one place holder growth is derived; the adaptive
+    /// filter and rules read it from `MintSignal::flow`.
+    pub fn enrich(&self, signal: &mut MintSignal) {
+        let mut mints = self.lock();
+        let flow = mints.entry(&signal.mint_address);
+        if let Some((holders, seen_at)) = flow.holders_seen {
+            let minutes = (signal.timestamp - seen_at).num_milliseconds() as f64 / 60_000.0;
+            if minutes > 0.0 {
+                flow.holder_growth_per_min =
+                    (signal.holder_count as f64 - holders as f64) / minutes;
+            }
+        }
+        flow.holders_seen = Some((signal.holder_count, signal.timestamp));
+        signal.flow = Some(self.compute(flow, signal.timestamp));
+    }
+
+    fn compute(&self, flow: &MintFlow, now: DateTime<Utc>) -> FlowFeatures {
+        let start = self.window_start(now);
+        let window_secs = self.window.as_secs_f64().max(f64::EPSILON);
+        let trades: Vec<&TradeEvent> = flow
+            .trades
+            .iter()
+            .filter(|t| t.timestamp >= start && t.timestamp <= now)
+            .collect();
+
+        let is_buy = |t: &&&TradeEvent| match t.kind {
+            TradeKind::Buy => true,
+            TradeKind::Create => t.token_amount > 0.0,
+            TradeKind::Sell => false,
+        };
+        let buys: Vec<&TradeEvent> = trades.iter().filter(is_buy).copied().collect();
+        let sells = trades.iter().filter(|t| t.kind == TradeKind::Sell).count();
+        let buy_sol: f64 = buys.iter().map(|t| t.sol_amount).sum();
+        let sell_sol: f64 = trades
+            .iter()
+            .filter(|t| t.kind == TradeKind::Sell)
+            .map(|t| t.sol_amount)
+            .sum();
+        let unique_buyers: HashSet<&str> = buys.iter().map(|t| t.trader.as_str()).collect();
+
+        let mid = start + (now - start) / 2;
+        let (early, late): (Vec<&TradeEvent>, Vec<&TradeEvent>) =
+            trades.iter().partition(|t| t.timestamp < mid);
+
+        FlowFeatures {
+            window_secs,
+            buys_per_sec: buys.len() as f64 / window_secs,
+            sells_per_sec: sells as f64 / window_secs,
+            unique_buyers: unique_buyers.len() as u64,
+            buy_sell_volume_ratio: if sell_sol > 0.0 {
+                (buy_sol / sell_sol).min(MAX_BUY_SELL_RATIO)
+            } else if buy_sol > 0.0 {
+                MAX_BUY_SELL_RATIO
+            } else {
+                0.0
+            },
+            holder_growth_per_min: flow.holder_growth_per_min,
+            price_velocity: velocity(&trades),
+            price_acceleration: (velocity(&late) - velocity(&early)) / (window_secs / 2.0),
+            avg_buy_sol: if buys.is_empty() {
+                0.0
+            } else {
+                buy_sol / buys.len() as f64
+            },
+        }
+    }
+}
+
+/// Relative price change per second between the first and last priced
+/// trade, 0 with fewer than two
+fn velocity(trades: &[&TradeEvent]) -> f64 {
+    let mut prices = trades
+        .iter()
+        .filter(|t| t.token_amount > 0.0 && t.sol_amount > 0.0)
+        .map(|t| (t.timestamp, t.sol_amount / t.token_amount));
+    let Some((first_at, first)) = prices.next() else {
+        return 0.0;
+    };
+    let Some((last_at, last)) = prices.next_back() else {
+        return 0.0;
+    };
+    let secs = (last_at - first_at).num_milliseconds() as f64 / 1000.0;
+    if secs <= 0.0 {
+        return 0.0;
+    }
+    (last / first - 1.0) / secs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::at;
+
+    fn trade(trader: &str, kind: TradeKind, sol: f64, tokens: f64, secs: i64) -> TradeEvent {
+        let mut event = TradeEvent::new("m".to_string(), trader.to_string(), kind, sol, tokens);
+        event.timestamp = at(secs);
+        event
+    }
+
+    #[test]
+    fn test_flow_counts_and_volume() {
+        let tracker = FlowTracker::new(Duration::from_secs(10));
+        // Outside the window by the time of the last trade
+        tracker.observe(&trade("old", TradeKind::Buy, 5.0, 100.0, 0));
+        tracker.observe(&trade("a", TradeKind::Buy, 1.0, 100.0, 20));
+        tracker.observe(&trade("b", TradeKind::Buy, 3.0, 100.0, 22));
+        tracker.observe(&trade("a", TradeKind::Buy, 2.0, 100.0, 25));
+        tracker.observe(&trade("c", TradeKind::Sell, 2.0, 100.0, 28));
+
+        let f = tracker.features("m", at(30)).unwrap();
+        assert!((f.buys_per_sec - 0.3).abs() < 1e-10);
+        assert!((f.sells_per_sec - 0.1).abs() < 1e-10);
+        assert_eq!(f.unique_buyers, 2);
+        assert!((f.buy_sell_volume_ratio - 3.0).abs() < 1e-10);
+        assert!((f.avg_buy_sol - 2.0).abs() < 1e-10);
+        assert!(tracker.features("other", at(30)).is_none());
+    }
+
+    #[test]
+    fn test_ratio_is_capped_without_sells() {
+        let tracker = FlowTracker::new(Duration::from_secs(10));
+        tracker.observe(&trade("a", TradeKind::Buy, 0.05, 100.0, 1));
+        let f = tracker.features("m", at(2)).unwrap();
+        assert_eq!(f.buy_sell_volume_ratio, MAX_BUY_SELL_RATIO);
+        assert_eq!(
+            tracker.features("m", at(60)).unwrap().buy_sell_volume_ratio,
+            0.0
+        );
+    }
+
+    #[test]
+    fn test_price_velocity_and_acceleration() {
+        let tracker = FlowTracker::new(Duration::from_secs(20));
+        // Flat for the first half, then +50% over 5 seconds
+        tracker.observe(&trade("a", TradeKind::Buy, 1.0, 100.0, 0));
+        tracker.observe(&trade("a", TradeKind::Buy, 1.0, 100.0, 5));
+        tracker.observe(&trade("b", TradeKind::Buy, 1.0, 100.0, 12));
+        tracker.observe(&trade("b", TradeKind::Buy, 1.5, 100.0, 17));
+
+        let f = tracker.features("m", at(20)).unwrap();
+        assert!((f.price_velocity - 0.5 / 17.0).abs() < 1e-10);
+        // Late half velocity 0.1/s, early half 0
+        assert!((f.price_acceleration - 0.01).abs() < 1e-10);
+    }
+
+    #[test]
+    fn test_enrich_tracks_holder_growth() {
+        let tracker = FlowTracker::new(Duration::from_secs(60));
+        let mut signal = MintSignal::new("m".to_string(), 1.0, 1.0, 1.0, 10, 1.0, 1.0);
+        signal.timestamp = at(0);
+        tracker.enrich(&mut signal);
+        assert_eq!(signal.flow.unwrap().holder_growth_per_min, 0.0);
+
+        signal.holder_count = 40;
+        signal.timestamp = at(120);
+        tracker.enrich(&mut signal);
+        assert!((signal.flow.unwrap().holder_growth_per_min - 15.0).abs() < 1e-10);
+    }
+}
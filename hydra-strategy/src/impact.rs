@@ -0,0 +1,264 @@
+use tracing::debug;
+
+use crate::error::StrategyError;
+
+/// pump.fun takes 1% of the SOL side of every trade
+pub const PUMP_FUN_FEE_BPS: u32 = 100;
+
+/// Every pump.fun token is minted with one billion tokens
+pub const PUMP_FUN_TOTAL_SUPPLY: f64 = 1_000_000_000.0;
+
+/// Constant-product view of a pump.fun bonding curve in SOL and whole tokens
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BondingCurve {
+    pub virtual_sol_reserves: f64,
+    pub virtual_token_reserves: f64,
+    /// Tokens left to sell on the curve, `None` if unknown
+    pub real_token_reserves: Option<f64>,
+    pub fee_bps: u32,
+}
+
+/// Expected result of one trade against the curve
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ImpactQuote {
+    /// SOL paid for a buy or received for a sell, fee included
+    pub sol_amount: f64,
+    pub tokens: f64,
+    pub fee_sol: f64,
+    /// Average SOL per token before fees
+    pub fill_price_sol: f64,
+    pub spot_price_sol: f64,
+    /// How far the average fill is from spot, ignoring fees
+    pub impact_bps: f64,
+}
+
+impl ImpactQuote {
+    pub fn fill_price_usd(&self, sol_usd: f64) -> f64 {
+        self.fill_price_sol * sol_usd
+    }
+}
+
+impl BondingCurve {
+    pub fn new(virtual_sol_reserves: f64, virtual_token_reserves: f64) -> Self {
+        Self {
+            virtual_sol_reserves,
+            virtual_token_reserves,
+            real_token_reserves: None,
+            fee_bps: PUMP_FUN_FEE_BPS,
+        }
+    }
+
+    pub fn with_fee_bps(mut self, fee_bps: u32) -> Self {
+        self.fee_bps = fee_bps;
+        self
+    }
+
+    pub fn with_real_token_reserves(mut self, real_token_reserves: f64) -> Self {
+        self.real_token_reserves = Some(real_token_reserves);
+        self
+    }
+
+    pub fn spot_price_sol(&self) -> f64 {
+        self.virtual_sol_reserves / self.virtual_token_reserves
+    }
+
+    fn fee_rate(&self) -> f64 {
+        self.fee_bps as f64 / 10_000.0
+    }
+
+    /// Spend up to `sol_in` (fee included) on the curve
+    pub fn quote_buy(&self, sol_in: f64) -> ImpactQuote {
+        let mut net_sol = sol_in * (1.0 - self.fee_rate());
+        let mut tokens =
+            self.virtual_token_reserves * net_sol / (self.virtual_sol_reserves + net_sol);
+        // The program only takes the SOL needed for the tokens that are left
+        if let Some(real) = self.real_token_reserves.filter(|r| tokens > *r) {
+            tokens = real.max(0.0);
+            net_sol = self.virtual_sol_reserves * tokens / (self.virtual_token_reserves - tokens);
+        }
+        let sol_in = net_sol / (1.0 - self.fee_rate());
+        self.quote(sol_in, net_sol, tokens, sol_in - net_sol)
+    }
+
+    /// Sell `tokens` back into the curve
+    pub fn quote_sell(&self, tokens: f64) -> ImpactQuote {
+        let gross_sol = self.virtual_sol_reserves * tokens / (self.virtual_token_reserves + tokens);
+        let fee_sol = gross_sol * self.fee_rate();
+        self.quote(gross_sol - fee_sol, gross_sol, tokens, fee_sol)
+    }
+
+    fn quote(&self, sol_amount: f64, net_sol: f64, tokens: f64, fee_sol: f64) -> ImpactQuote {
+        let spot = self.spot_price_sol();
+        let fill = if tokens > 0.0 { net_sol / tokens } else { spot };
+        ImpactQuote {
+            sol_amount,
+            tokens,
+            fee_sol,
+            fill_price_sol: fill,
+            spot_price_sol: spot,
+            impact_bps: (fill / spot - 1.0).abs() * 10_000.0,
+        }
+    }
+
+    /// Curve state after a buy has filled
+    pub fn after_buy(&self, quote: &ImpactQuote) -> Self {
+        Self {
+            virtual_sol_reserves: self.virtual_sol_reserves + quote.sol_amount - quote.fee_sol,
+            virtual_token_reserves: self.virtual_token_reserves - quote.tokens,
+            real_token_reserves: self.real_token_reserves.map(|r| r - quote.tokens),
+            fee_bps: self.fee_bps,
+        }
+    }
+
+    /// Largest buy (fee included) whose average fill stays within
+    /// `max_impact_bps` of spot. For a constant-product curve the buy
+    /// impact is exactly `net_sol / virtual_sol_reserves`.
+    pub fn max_buy_sol(&self, max_impact_bps: u32) -> f64 {
+        let net_sol = self.virtual_sol_reserves * max_impact_bps as f64 / 10_000.0;
+        net_sol / (1.0 - self.fee_rate())
+    }
+}
+
+/// A buy that fits the impact limit together with the exit it implies
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ImpactSizedOrder {
+    pub size_sol: f64,
+    pub entry: ImpactQuote,
+    /// Selling every token received right after the entry
+    pub exit: ImpactQuote,
+    /// True when the requested size was reduced to meet the limit
+    pub capped: bool,
+}
+
+/// Keeps expected slippage on entry under `max_impact_bps`, and
+/// optionally the slippage of entering and fully exiting under
+/// `max_round_trip_bps`. On a constant-product curve the exit alone
+/// always slips less than the entry, so the exit is judged by the
+/// combined cost of both legs.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ImpactLimit {
+    pub max_impact_bps: u32,
+    pub max_round_trip_bps: Option<u32>,
+}
+
+impl ImpactLimit {
+    pub fn new(max_impact_bps: u32) -> Self {
+        Self {
+            max_impact_bps,
+            max_round_trip_bps: None,
+        }
+    }
+
+    pub fn with_max_round_trip_bps(mut self, max_round_trip_bps: u32) -> Self {
+        self.max_round_trip_bps = Some(max_round_trip_bps);
+        self
+    }
+
+    pub fn size_order(
+        &self,
+        curve: &BondingCurve,
+        desired_sol: f64,
+    ) -> Result<ImpactSizedOrder, StrategyError> {
+        let max_sol = curve.max_buy_sol(self.max_impact_bps);
+        let capped = desired_sol > max_sol;
+        let size_sol = desired_sol.min(max_sol);
+
+        let entry = curve.quote_buy(size_sol);
+        let exit = curve.after_buy(&entry).quote_sell(entry.tokens);
+        let round_trip_bps = entry.impact_bps + exit.impact_bps;
+        if let Some(max_bps) = self.max_round_trip_bps {
+            if round_trip_bps > max_bps as f64 + 1e-6 {
+                return Err(StrategyError::ExitInfeasible {
+                    tokens: entry.tokens,
+                    impact_bps: round_trip_bps,
+                    max_bps,
+                });
+            }
+        }
+
+        debug!(
+            desired_sol,
+            size_sol,
+            capped,
+            tokens = entry.tokens,
+            entry_impact_bps = entry.impact_bps,
+            exit_impact_bps = exit.impact_bps,
+            round_trip_bps,
+            "Order sized against bonding curve"
+        );
+        Ok(ImpactSizedOrder {
+            size_sol,
+            entry,
+            exit,
+            capped,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Fresh pump.fun curve: 30 SOL and 1.073B tokens virtual
+    fn fresh() -> BondingCurve {
+        BondingCurve::new(30.0, 1_073_000_000.0).with_real_token_reserves(793_100_000.0)
+    }
+
+    #[test]
+    fn test_buy_quote_matches_constant_product() {
+        let curve = fresh().with_fee_bps(0);
+        let quote = curve.quote_buy(3.0);
+        // k = 30 * 1.073e9, tokens = vt - k / 33
+        let expected = 1_073_000_000.0 - 30.0 * 1_073_000_000.0 / 33.0;
+        assert!((quote.tokens - expected).abs() < 1e-3);
+        assert!((quote.impact_bps - 1_000.0).abs() < 1e-6);
+        assert!(quote.fill_price_sol > curve.spot_price_sol());
+    }
+
+    #[test]
+    fn test_round_trip_loses_fees_only() {
+        let curve = fresh();
+        let buy = curve.quote_buy(1.0);
+        let sell = curve.after_buy(&buy).quote_sell(buy.tokens);
+        // 1% in, 1% out, nothing else lost on a constant-product curve
+        assert!((sell.sol_amount - 0.99 * 0.99).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_order_capped_to_impact_limit() {
+        let limit = ImpactLimit::new(300);
+        let order = limit.size_order(&fresh(), 5.0).unwrap();
+        assert!(order.capped);
+        assert!((order.entry.impact_bps - 300.0).abs() < 1e-6);
+        assert!(order.exit.impact_bps <= 300.0);
+
+        let small = limit.size_order(&fresh(), 0.1).unwrap();
+        assert!(!small.capped);
+        assert_eq!(small.size_sol, 0.1);
+        assert!(small.entry.tokens > 0.0);
+    }
+
+    #[test]
+    fn test_buy_limited_by_real_token_reserves() {
+        let curve = fresh()
+            .with_fee_bps(0)
+            .with_real_token_reserves(1_000_000.0);
+        let quote = curve.quote_buy(10.0);
+        assert_eq!(quote.tokens, 1_000_000.0);
+        assert!(quote.sol_amount < 0.03);
+    }
+
+    #[test]
+    fn test_round_trip_limit_rejects_exit() {
+        // Entry capped at 300 bps; selling it all back adds almost as much
+        let limit = ImpactLimit::new(300).with_max_round_trip_bps(400);
+        let err = limit.size_order(&fresh(), 5.0).unwrap_err();
+        let StrategyError::ExitInfeasible { impact_bps, .. } = err else {
+            panic!("expected ExitInfeasible, got {err}");
+        };
+        assert!(impact_bps > 500.0 && impact_bps < 600.0);
+
+        let order = limit.size_order(&fresh(), 0.1).unwrap();
+        assert!(order.entry.impact_bps + order.exit.impact_bps <= 400.0);
+    }
+}
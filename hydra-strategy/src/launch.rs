@@ -0,0 +1,368 @@
+use chrono::{DateTime, Utc};
+use hydra_core::signal::{MintSignal, TradeEvent, TradeKind};
+use hydra_core::traits::ScoredSignal;
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tracing::debug;
+
+use crate::filters::{Decision, Filter};
+use crate::impact::PUMP_FUN_TOTAL_SUPPLY;
+use crate::mint_table::MintTable;
+
+/// What the first trades of a launch looked like
+#[derive(Debug, Clone, PartialEq)]
+pub struct LaunchReport {
+    pub trades_seen: usize,
+    /// Distinct wallets, the creator included, that bought in the
+    /// creation slot, or within the same-slot window of the create when
+    /// the feed carries no slots
+    pub same_slot_buyers: usize,
+    /// Share of total supply bought in the creation slot, creator included
+    pub same_slot_supply_pct: f64,
+    pub dev_supply_pct: f64,
+    /// Most early buyers funded from one source
+    pub largest_funded_cluster: usize,
+    pub cluster_supply_pct: f64,
+}
+
+#[derive(Debug, Default)]
+struct EarlyTrades {
+    creator: Option<String>,
+    create_slot: Option<u64>,
+    created_at: Option<DateTime<Utc>>,
+    trades: Vec<TradeEvent>,
+}
+
+/// Records the first `first_trades` trades after each `TokenCreated`.
+/// Wallet funding sources are optional and come from whoever knows them.
+pub struct LaunchAnalyzer {
+    first_trades: usize,
+    same_slot_window: Duration,
+    launches: Mutex<MintTable<EarlyTrades>>,
+    funders: Mutex<HashMap<String, String>>,
+}
+
+impl LaunchAnalyzer {
+    pub fn new(first_trades: usize) -> Self {
+        Self {
+            first_trades,
+            same_slot_window: Duration::from_millis(1_000),
+            launches: Mutex::new(MintTable::default()),
+            funders: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// How close to the create a trade must be to count as same-slot when
+    /// either side has no slot. PumpPortal trade messages carry none.
+    pub fn with_same_slot_window(mut self, window: Duration) -> Self {
+        self.same_slot_window = window;
+        self
+    }
+
+    /// Remember that `wallet` was funded by `funder`
+    pub fn record_funding(&self, wallet: String, funder: String) {
+        self.funders
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(wallet, funder);
+    }
+
+    pub fn observe(&self, event: &TradeEvent) {
+        let mut launches = self.launches.lock().unwrap_or_else(|e| e.into_inner());
+        if event.kind == TradeKind::Create {
+            if launches
+                .get(&event.mint_address)
+                .is_some_and(|l| l.creator.is_some())
+            {
+                debug!(mint = %event.mint_address, "Duplicate create ignored");
+                return;
+            }
+            let launch = launches.entry(&event.mint_address);
+            launch.creator = Some(event.trader.clone());
+            launch.create_slot = event.slot;
+            launch.created_at = Some(event.timestamp);
+        }
+        // Trades of mints whose create event was missed are ignored
+        let Some(launch) = launches.get_mut(&event.mint_address) else {
+            return;
+        };
+        if launch.trades.len() < self.first_trades {
+            launch.trades.push(event.clone());
+        }
+    }
+
+    /// `None` until the create event of the mint has been seen
+    pub fn report(&self, mint_address: &str) -> Option<LaunchReport> {
+        let launches = self.launches.lock().unwrap_or_else(|e| e.into_inner());
+        let launch = launches.get(mint_address)?;
+        let creator = launch.creator.as_deref()?;
+        let funders = self.funders.lock().unwrap_or_else(|e| e.into_inner());
+
+        let buys = || launch.trades.iter().filter(|t| t.kind != TradeKind::Sell);
+        let supply_pct = |tokens: f64| tokens / PUMP_FUN_TOTAL_SUPPLY * 100.0;
+
+        let window = chrono::Duration::from_std(self.same_slot_window).unwrap_or_default();
+        let in_create_slot = |t: &&TradeEvent| match (launch.create_slot, t.slot) {
+            (Some(create), Some(slot)) => slot == create,
+            _ => launch
+                .created_at
+                .is_some_and(|at| (t.timestamp - at).abs() <= window),
+        };
+        let same_slot: Vec<&TradeEvent> = buys().filter(in_create_slot).collect();
+        let same_slot_buyers: HashSet<&str> = same_slot.iter().map(|t| t.trader.as_str()).collect();
+
+        let mut clusters: HashMap<&str, (HashSet<&str>, f64)> = HashMap::new();
+        for trade in buys() {
+            if let Some(funder) = funders.get(&trade.trader) {
+                let cluster = clusters.entry(funder.as_str()).or_default();
+                cluster.0.insert(trade.trader.as_str());
+                cluster.1 += trade.token_amount;
+            }
+        }
+        let (largest_funded_cluster, cluster_tokens) = clusters
+            .values()
+            .map(|(wallets, tokens)| (wallets.len(), *tokens))
+            .max_by(|a, b| a.0.cmp(&b.0).then(a.1.total_cmp(&b.1)))
+            .unwrap_or((0, 0.0));
+
+        Some(LaunchReport {
+            trades_seen: launch.trades.len(),
+            same_slot_buyers: same_slot_buyers.len(),
+            same_slot_supply_pct: supply_pct(same_slot.iter().map(|t| t.token_amount).sum()),
+            dev_supply_pct: supply_pct(
+                buys()
+                    .filter(|t| t.trader == creator)
+                    .map(|t| t.token_amount)
+                    .sum(),
+            ),
+            largest_funded_cluster,
+            cluster_supply_pct: supply_pct(cluster_tokens),
+        })
+    }
+}
+
+/// What `SniperFilter` does with a launch that breaches a threshold
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SniperAction {
+    Reject,
+    /// Let the signal through and multiply its score by `1 - penalty`
+    /// per breached threshold
+    DownScore {
+        penalty: f64,
+    },
+}
+
+/// Upper bounds for a clean launch. Supply shares are in percent.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SniperThresholds {
+    pub max_same_slot_buyers: usize,
+    pub max_same_slot_supply_pct: f64,
+    pub max_dev_supply_pct: f64,
+    pub max_funded_cluster: usize,
+}
+
+impl Default for SniperThresholds {
+    fn default() -> Self {
+        Self {
+            max_same_slot_buyers: 3,
+            max_same_slot_supply_pct: 15.0,
+            max_dev_supply_pct: 10.0,
+            max_funded_cluster: 3,
+        }
+    }
+}
+
+/// Flags launches where the dev or a bundle of wallets bought a large
+/// share of supply in the first slot
+pub struct SniperFilter {
+    analyzer: Arc<LaunchAnalyzer>,
+    thresholds: SniperThresholds,
+    action: SniperAction,
+}
+
+impl SniperFilter {
+    pub fn new(
+        analyzer: Arc<LaunchAnalyzer>,
+        thresholds: SniperThresholds,
+        action: SniperAction,
+    ) -> Self {
+        Self {
+            analyzer,
+            thresholds,
+            action,
+        }
+    }
+
+    /// Every breached threshold as `(reason, value, threshold)`
+    pub fn breaches(&self, mint_address: &str) -> Vec<(&'static str, f64, f64)> {
+        let Some(report) = self.analyzer.report(mint_address) else {
+            return Vec::new();
+        };
+        let t = &self.thresholds;
+        let checks = [
+            (
+                "same_slot_buyers",
+                report.same_slot_buyers as f64,
+                t.max_same_slot_buyers as f64,
+            ),
+            (
+                "same_slot_supply",
+                report.same_slot_supply_pct,
+                t.max_same_slot_supply_pct,
+            ),
+            ("dev_supply", report.dev_supply_pct, t.max_dev_supply_pct),
+            (
+                "funded_cluster",
+                report.largest_funded_cluster as f64,
+                t.max_funded_cluster as f64,
+            ),
+        ];
+        checks
+            .into_iter()
+            .filter(|(_, value, max)| value > max)
+            .collect()
+    }
+
+    /// Apply the down-score for breached thresholds. Returns how many
+    /// were breached; the score is untouched in `Reject` mode.
+    pub fn adjust_score(&self, scored: &mut ScoredSignal) -> usize {
+        let breaches = self.breaches(&scored.signal.mint_address);
+        if let SniperAction::DownScore { penalty } = self.action {
+            let factor = (1.0 - penalty).clamp(0.0, 1.0).powi(breaches.len() as i32);
+            if !breaches.is_empty() {
+                debug!(
+                    mint = %scored.signal.mint_address,
+                    breaches = breaches.len(),
+                    score = scored.score,
+                    factor,
+                    "Sniper down-score"
+                );
+            }
+            scored.score *= factor;
+        }
+        breaches.len()
+    }
+}
+
+impl Filter for SniperFilter {
+    fn name(&self) -> &str {
+        "sniper"
+    }
+
+    fn evaluate(&self, signal: &MintSignal) -> Decision {
+        if self.action != SniperAction::Reject {
+            return Decision::Pass;
+        }
+        match self.breaches(&signal.mint_address).first() {
+            Some((reason, value, threshold)) => {
+                Decision::reject(self.name(), reason, *value, *threshold)
+            }
+            None => Decision::Pass,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::at_millis;
+
+    fn trade(trader: &str, kind: TradeKind, tokens: f64, slot: u64) -> TradeEvent {
+        TradeEvent::new("m".to_string(), trader.to_string(), kind, 1.0, tokens).with_slot(slot)
+    }
+
+    fn signal() -> MintSignal {
+        MintSignal::new(
+            "m".to_string(),
+            10_000.0,
+            5_000.0,
+            0.001,
+            100,
+            5_000.0,
+            10.0,
+        )
+    }
+
+    /// Dev buys 5%, three bundle wallets 4% each in the creation slot,
+    /// then a late buyer
+    fn bundled() -> Arc<LaunchAnalyzer> {
+        let analyzer = Arc::new(LaunchAnalyzer::new(10));
+        analyzer.observe(&trade("dev", TradeKind::Create, 50_000_000.0, 100));
+        for wallet in ["b1", "b2", "b3"] {
+            analyzer.observe(&trade(wallet, TradeKind::Buy, 40_000_000.0, 100));
+            analyzer.record_funding(wallet.to_string(), "funder".to_string());
+        }
+        analyzer.observe(&trade("late", TradeKind::Buy, 10_000_000.0, 105));
+        analyzer
+    }
+
+    #[test]
+    fn test_report_of_bundled_launch() {
+        let report = bundled().report("m").unwrap();
+        assert_eq!(report.trades_seen, 5);
+        assert_eq!(report.same_slot_buyers, 4);
+        assert!((report.same_slot_supply_pct - 17.0).abs() < 1e-9);
+        assert!((report.dev_supply_pct - 5.0).abs() < 1e-9);
+        assert_eq!(report.largest_funded_cluster, 3);
+        assert!((report.cluster_supply_pct - 12.0).abs() < 1e-9);
+        assert_eq!(bundled().report("other"), None);
+    }
+
+    #[test]
+    fn test_filter_rejects_bundle() {
+        let filter =
+            SniperFilter::new(bundled(), SniperThresholds::default(), SniperAction::Reject);
+        assert!(matches!(
+            filter.evaluate(&signal()),
+            Decision::Reject { reason, value, .. } if reason == "same_slot_buyers" && value == 4.0
+        ));
+        assert_eq!(filter.breaches("m").len(), 2);
+
+        let lenient = SniperThresholds {
+            max_same_slot_buyers: 10,
+            max_same_slot_supply_pct: 50.0,
+            ..Default::default()
+        };
+        assert!(SniperFilter::new(bundled(), lenient, SniperAction::Reject)
+            .evaluate(&signal())
+            .is_pass());
+    }
+
+    #[test]
+    fn test_down_score_mode() {
+        let filter = SniperFilter::new(
+            bundled(),
+            SniperThresholds::default(),
+            SniperAction::DownScore { penalty: 0.5 },
+        );
+        assert!(filter.evaluate(&signal()).is_pass());
+        let mut scored = ScoredSignal {
+            signal: signal(),
+            score: 0.8,
+            should_buy: true,
+        };
+        assert_eq!(filter.adjust_score(&mut scored), 2);
+        assert!((scored.score - 0.2).abs() < 1e-10);
+    }
+
+    #[test]
+    fn test_without_slots_uses_time_window_and_ignores_duplicate_create() {
+        let event = |trader: &str, kind: TradeKind, millis: i64| {
+            let mut event =
+                TradeEvent::new("m".to_string(), trader.to_string(), kind, 1.0, 40_000_000.0);
+            event.timestamp = at_millis(millis);
+            event
+        };
+        let analyzer = LaunchAnalyzer::new(10);
+        analyzer.observe(&event("dev", TradeKind::Create, 0));
+        analyzer.observe(&event("dev", TradeKind::Create, 100));
+        analyzer.observe(&event("b1", TradeKind::Buy, 400));
+        analyzer.observe(&event("late", TradeKind::Buy, 3_000));
+
+        let report = analyzer.report("m").unwrap();
+        assert_eq!(report.trades_seen, 3);
+        assert_eq!(report.same_slot_buyers, 2);
+        assert!((report.dev_supply_pct - 4.0).abs() < 1e-9);
+    }
+}
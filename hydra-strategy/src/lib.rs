@@ -1,5 +1,49 @@
+pub mod adaptive;
+pub mod chain;
+pub mod error;
 pub mod filters;
+pub mod flow;
+pub mod impact;
+pub mod launch;
+pub mod metrics;
+mod mint_table;
+pub mod reentry;
+pub mod reputation;
+pub mod rules;
+pub mod runner;
+pub mod sizing;
+#[cfg(test)]
+mod test_util;
 pub mod tpsl;
+pub mod volatility;
+pub mod watchlist;
 
-pub use filters::{McapFilter, RugCheckFilter, ZScoreFilter};
-pub use tpsl::TpSlCalculator;
+pub use adaptive::{AdaptiveZScoreFilter, Estimator, Feature, RollingStats};
+pub use chain::{ChainMode, ChainOutcome, FilterChain};
+pub use error::StrategyError;
+pub use filters::{Decision, Filter, McapFilter, MetadataFilter, RugCheckFilter, ZScoreFilter};
+pub use flow::FlowTracker;
+pub use impact::{
+    BondingCurve, ImpactLimit, ImpactQuote, ImpactSizedOrder, PUMP_FUN_FEE_BPS,
+    PUMP_FUN_TOTAL_SUPPLY,
+};
+pub use launch::{LaunchAnalyzer, LaunchReport, SniperAction, SniperFilter, SniperThresholds};
+pub use metrics::{FilterMetrics, RejectionLabels};
+pub use reentry::{MintHistory, ReentryGuard, ReentryPolicy};
+pub use reputation::{
+    CreatorReputationFilter, CreatorStats, ReputationStore, ReputationThresholds, RugRule,
+};
+pub use rules::{Rule, RuleConfig, RuleFilter};
+pub use runner::{Allocation, Strategy, StrategyBook, StrategyEntry, StrategyRunner};
+pub use sizing::{
+    ConfidenceScaledSizer, FixedFractionSizer, FixedSolSizer, FractionalKellySizer, KellyStats,
+    PositionSizer, PositionSizing, SizingContext,
+};
+pub use tpsl::{
+    ExitEvaluator, ExitPlan, ExitReason, MinProgress, SellInstruction, TimeExits, TpSlCalculator,
+    TpSlLevels, TpTranche, TrailingStop,
+};
+pub use volatility::{measure_volatility, VolatilityMeasure, VolatilityTpSlCalculator};
+pub use watchlist::{
+    Candidate, ConfirmCriteria, ConfirmWindow, WatchOutcome, WatchRecord, WatchStats, Watchlist,
+};
@@ -0,0 +1,57 @@
+use prometheus_client::{
+    encoding::EncodeLabelSet,
+    metrics::{counter::Counter, family::Family},
+    registry::Registry,
+};
+
+#[derive(Debug, Clone, Hash, PartialEq, Eq, EncodeLabelSet)]
+pub struct RejectionLabels {
+    pub filter: String,
+    pub reason: String,
+}
+
+#[derive(Clone)]
+pub struct FilterMetrics {
+    pub signals_evaluated: Counter,
+    pub signals_passed: Counter,
+    pub rejections: Family<RejectionLabels, Counter>,
+}
+
+impl FilterMetrics {
+    pub fn new(registry: &mut Registry) -> Self {
+        let signals_evaluated: Counter = Counter::default();
+        let signals_passed: Counter = Counter::default();
+        let rejections = Family::<RejectionLabels, Counter>::default();
+
+        registry.register(
+            "hydra_filter_signals_evaluated",
+            "Signals run through the filter chain",
+            signals_evaluated.clone(),
+        );
+        registry.register(
+            "hydra_filter_signals_passed",
+            "Signals that passed every filter",
+            signals_passed.clone(),
+        );
+        registry.register(
+            "hydra_filter_rejections",
+            "Filter rejections by filter and reason",
+            rejections.clone(),
+        );
+
+        Self {
+            signals_evaluated,
+            signals_passed,
+            rejections,
+        }
+    }
+
+    pub fn rejection_count(&self, filter: &str, reason: &str) -> u64 {
+        self.rejections
+            .get_or_create(&RejectionLabels {
+                filter: filter.to_string(),
+                reason: reason.to_string(),
+            })
+            .get()
+    }
+}
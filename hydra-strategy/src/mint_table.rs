@@ -0,0 +1,113 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::ops::Deref;
+use tracing::info;
+
+/// Mints a table follows before entries are dropped
+pub(crate) const MAX_TRACKED_MINTS: usize = 50_000;
+
+/// Per-mint state that a flood of new mints cannot grow without bound.
+/// Reads go through `Deref`; adding a mint to a full table first drops
+/// entries, everything unless `make_room` was told what to keep.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(transparent)]
+pub(crate) struct MintTable<V> {
+    mints: HashMap<String, V>,
+}
+
+impl<V> Default for MintTable<V> {
+    fn default() -> Self {
+        Self {
+            mints: HashMap::new(),
+        }
+    }
+}
+
+impl<V> Deref for MintTable<V> {
+    type Target = HashMap<String, V>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.mints
+    }
+}
+
+impl<V> MintTable<V> {
+    /// Whether `mint_address` can be added without dropping anything
+    pub(crate) fn has_room_for(&self, mint_address: &str) -> bool {
+        self.mints.len() < MAX_TRACKED_MINTS || self.mints.contains_key(mint_address)
+    }
+
+    /// Make room for `mint_address` by dropping the entries `keep`
+    /// rejects, and all of them if that is not enough
+    pub(crate) fn make_room(&mut self, mint_address: &str, mut keep: impl FnMut(&V) -> bool) {
+        if self.has_room_for(mint_address) {
+            return;
+        }
+        let before = self.mints.len();
+        self.mints.retain(|_, v| keep(v));
+        if self.mints.len() >= MAX_TRACKED_MINTS {
+            self.mints.clear();
+        }
+        info!(
+            table = std::any::type_name::<V>(),
+            dropped = before - self.mints.len(),
+            "Mint table pruned"
+        );
+    }
+
+    pub(crate) fn get_mut(&mut self, mint_address: &str) -> Option<&mut V> {
+        self.mints.get_mut(mint_address)
+    }
+
+    pub(crate) fn iter_mut(&mut self) -> impl Iterator<Item = (&String, &mut V)> {
+        self.mints.iter_mut()
+    }
+
+    pub(crate) fn remove(&mut self, mint_address: &str) -> Option<V> {
+        self.mints.remove(mint_address)
+    }
+
+    pub(crate) fn insert(&mut self, mint_address: String, value: V) {
+        self.make_room(&mint_address, |_| false);
+        self.mints.insert(mint_address, value);
+    }
+
+    pub(crate) fn entry(&mut self, mint_address: &str) -> &mut V
+    where
+        V: Default,
+    {
+        self.make_room(mint_address, |_| false);
+        self.mints.entry(mint_address.to_string()).or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_full_table_keeps_what_it_is_told_to() {
+        let mut table = MintTable::<u32>::default();
+        for i in 0..MAX_TRACKED_MINTS as u32 {
+            table.insert(i.to_string(), i);
+        }
+        *table.entry("0") += 1;
+        assert_eq!(table.len(), MAX_TRACKED_MINTS);
+
+        table.make_room("new", |v| *v < 10);
+        assert_eq!(table.len(), 10);
+        assert_eq!(table.get("0"), Some(&1));
+    }
+
+    #[test]
+    fn test_full_table_is_cleared_when_nothing_can_go() {
+        let mut table = MintTable::<u32>::default();
+        for i in 0..MAX_TRACKED_MINTS as u32 {
+            table.insert(i.to_string(), i);
+        }
+        table.make_room("new", |_| true);
+        assert!(table.is_empty());
+        *table.entry("new") += 1;
+        assert_eq!(table.len(), 1);
+    }
+}
@@ -0,0 +1,278 @@
+use chrono::{DateTime, Utc};
+use hydra_core::position::CompletedTrade;
+use hydra_core::signal::MintSignal;
+use std::sync::Mutex;
+use std::time::Duration;
+use tracing::warn;
+
+use crate::filters::{Decision, Filter};
+use crate::mint_table::MintTable;
+
+/// What we have done on one mint so far
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MintHistory {
+    pub entries: u32,
+    /// Entries submitted or held that have not exited yet
+    pub active: u32,
+    pub last_entry_at: Option<DateTime<Utc>>,
+    pub last_exit_at: Option<DateTime<Utc>>,
+    pub last_exit_reason: Option<String>,
+    pub last_pnl_sol: Option<f64>,
+    pub banned: bool,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReentryPolicy {
+    /// Wait this long after a losing exit before entering again
+    pub loss_cooldown: Duration,
+    pub max_entries: u32,
+    /// An exit losing at least this fraction of the position is treated
+    /// as a rug and bans the mint
+    pub rug_loss_pct: f64,
+    /// Exit reasons that ban the mint regardless of the loss
+    pub rug_reasons: Vec<String>,
+}
+
+impl Default for ReentryPolicy {
+    fn default() -> Self {
+        Self {
+            loss_cooldown: Duration::from_secs(30 * 60),
+            max_entries: 2,
+            rug_loss_pct: 0.5,
+            rug_reasons: Vec::new(),
+        }
+    }
+}
+
+/// Blocks entries on a mint that is already being traded, was just lost
+/// on, has been entered too often, or rugged us. Works per mint across
+/// all strategies.
+pub struct ReentryGuard {
+    policy: ReentryPolicy,
+    history: Mutex<MintTable<MintHistory>>,
+}
+
+impl ReentryGuard {
+    pub fn new(policy: ReentryPolicy) -> Self {
+        Self {
+            policy,
+            history: Mutex::new(MintTable::default()),
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, MintTable<MintHistory>> {
+        self.history.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Record an entry as soon as its order is submitted
+    pub fn on_entry(&self, mint_address: &str, at: DateTime<Utc>) {
+        Self::record_entry(&mut self.lock(), mint_address, at);
+    }
+
+    /// `check` and, if it passes, `on_entry` under one lock, so two
+    /// signals on the same mint cannot both get in
+    pub fn try_enter(&self, mint_address: &str, now: DateTime<Utc>) -> Decision {
+        let mut history = self.lock();
+        let decision = self.decide(history.get(mint_address), now);
+        if decision.is_pass() {
+            Self::record_entry(&mut history, mint_address, now);
+        }
+        decision
+    }
+
+    fn record_entry(history: &mut MintTable<MintHistory>, mint_address: &str, at: DateTime<Utc>) {
+        let h = Self::entry(history, mint_address);
+        h.entries += 1;
+        h.active += 1;
+        h.last_entry_at = Some(at);
+    }
+
+    /// History of `mint_address`. A full table drops idle histories
+    /// first and keeps open positions and bans.
+    fn entry<'a>(
+        history: &'a mut MintTable<MintHistory>,
+        mint_address: &str,
+    ) -> &'a mut MintHistory {
+        history.make_room(mint_address, |h| h.active > 0 || h.banned);
+        history.entry(mint_address)
+    }
+
+    /// Undo `on_entry` for an order that never filled
+    pub fn on_entry_failed(&self, mint_address: &str) {
+        if let Some(h) = self.lock().get_mut(mint_address) {
+            h.entries = h.entries.saturating_sub(1);
+            h.active = h.active.saturating_sub(1);
+        }
+    }
+
+    pub fn on_exit(&self, trade: &CompletedTrade) {
+        let mut history = self.lock();
+        let h = Self::entry(&mut history, &trade.mint_address);
+        h.active = h.active.saturating_sub(1);
+        h.last_exit_at = Some(trade.closed_at);
+        h.last_exit_reason = Some(trade.exit_reason.clone());
+        h.last_pnl_sol = Some(trade.pnl_sol);
+
+        let loss_pct = if trade.size_sol > 0.0 {
+            -trade.pnl_sol / trade.size_sol
+        } else {
+            0.0
+        };
+        if loss_pct >= self.policy.rug_loss_pct
+            || self.policy.rug_reasons.contains(&trade.exit_reason)
+        {
+            h.banned = true;
+            warn!(
+                mint = %trade.mint_address,
+                loss_pct,
+                reason = %trade.exit_reason,
+                "Mint banned after rug-like exit"
+            );
+        }
+    }
+
+    pub fn history(&self, mint_address: &str) -> Option<MintHistory> {
+        self.lock().get(mint_address).cloned()
+    }
+
+    /// Decision for entering `mint_address` at `now`, without recording
+    /// anything. Use `try_enter` to act on it.
+    pub fn check(&self, mint_address: &str, now: DateTime<Utc>) -> Decision {
+        self.decide(self.lock().get(mint_address), now)
+    }
+
+    fn decide(&self, history: Option<&MintHistory>, now: DateTime<Utc>) -> Decision {
+        let Some(h) = history else {
+            return Decision::Pass;
+        };
+        if h.banned {
+            return Decision::reject(self.name(), "banned", f64::NAN, f64::NAN);
+        }
+        if h.active > 0 {
+            return Decision::reject(self.name(), "in_flight", h.active as f64, 0.0);
+        }
+        if h.entries >= self.policy.max_entries {
+            return Decision::reject(
+                self.name(),
+                "max_entries",
+                h.entries as f64,
+                self.policy.max_entries as f64,
+            );
+        }
+        if let (Some(exit_at), Some(pnl)) = (h.last_exit_at, h.last_pnl_sol) {
+            let since = (now - exit_at).to_std().unwrap_or_default();
+            if pnl < 0.0 && since < self.policy.loss_cooldown {
+                return Decision::reject(
+                    self.name(),
+                    "loss_cooldown",
+                    since.as_secs_f64(),
+                    self.policy.loss_cooldown.as_secs_f64(),
+                );
+            }
+        }
+        Decision::Pass
+    }
+}
+
+impl Filter for ReentryGuard {
+    fn name(&self) -> &str {
+        "reentry"
+    }
+
+    fn evaluate(&self, signal: &MintSignal) -> Decision {
+        self.check(&signal.mint_address, Utc::now())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::at;
+
+    fn exit(pnl_sol: f64, reason: &str, secs: i64) -> CompletedTrade {
+        CompletedTrade {
+            position_id: "p".to_string(),
+            mint_address: "m".to_string(),
+            entry_price_usd: 1.0,
+            exit_price_usd: 1.0,
+            size_sol: 1.0,
+            pnl_sol,
+            opened_at: at(0),
+            closed_at: at(secs),
+            exit_reason: reason.to_string(),
+            strategy_id: None,
+        }
+    }
+
+    fn reason(decision: Decision) -> String {
+        match decision {
+            Decision::Reject { reason, .. } => reason,
+            Decision::Pass => "pass".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_in_flight_and_loss_cooldown() {
+        let guard = ReentryGuard::new(ReentryPolicy::default());
+        assert!(guard.check("m", at(0)).is_pass());
+        guard.on_entry("m", at(0));
+        assert_eq!(reason(guard.check("m", at(1))), "in_flight");
+
+        guard.on_exit(&exit(-0.2, "stop_loss", 60));
+        assert_eq!(reason(guard.check("m", at(120))), "loss_cooldown");
+        assert!(guard.check("m", at(60 + 30 * 60)).is_pass());
+
+        let h = guard.history("m").unwrap();
+        assert_eq!(h.entries, 1);
+        assert_eq!(h.last_exit_reason.as_deref(), Some("stop_loss"));
+    }
+
+    #[test]
+    fn test_try_enter_admits_one_of_concurrent_signals() {
+        let guard = ReentryGuard::new(ReentryPolicy::default());
+        let admitted = std::sync::atomic::AtomicUsize::new(0);
+        std::thread::scope(|scope| {
+            for _ in 0..8 {
+                scope.spawn(|| {
+                    if guard.try_enter("m", at(0)).is_pass() {
+                        admitted.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+                    }
+                });
+            }
+        });
+        assert_eq!(admitted.into_inner(), 1);
+        assert_eq!(guard.history("m").unwrap().active, 1);
+        assert_eq!(reason(guard.try_enter("m", at(1))), "in_flight");
+    }
+
+    #[test]
+    fn test_max_entries_and_failed_orders() {
+        let guard = ReentryGuard::new(ReentryPolicy::default());
+        guard.on_entry("m", at(0));
+        guard.on_entry_failed("m");
+        assert!(guard.check("m", at(1)).is_pass());
+
+        for i in 0..2 {
+            guard.on_entry("m", at(i * 100));
+            guard.on_exit(&exit(0.1, "take_profit", i * 100 + 50));
+        }
+        assert_eq!(reason(guard.check("m", at(300))), "max_entries");
+    }
+
+    #[test]
+    fn test_rug_like_exit_bans_mint() {
+        let policy = ReentryPolicy {
+            rug_reasons: vec!["rug_detected".to_string()],
+            ..Default::default()
+        };
+        let guard = ReentryGuard::new(policy);
+        guard.on_entry("m", at(0));
+        guard.on_exit(&exit(-0.7, "stop_loss", 10));
+        assert_eq!(reason(guard.check("m", at(100_000))), "banned");
+
+        let mut trade = exit(-0.01, "rug_detected", 10);
+        trade.mint_address = "other".to_string();
+        guard.on_exit(&trade);
+        assert!(guard.history("other").unwrap().banned);
+    }
+}
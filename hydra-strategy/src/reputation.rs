@@ -0,0 +1,505 @@
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use hydra_core::position::CompletedTrade;
+use hydra_core::signal::{MintSignal, TradeEvent, TradeKind};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tracing::{info, warn};
+
+use crate::filters::{Decision, Filter};
+use crate::mint_table::MintTable;
+
+/// When a creator's selling counts as a rug: at least `dev_sell_pct` of
+/// the tokens they bought, sold within `within` of the launch
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RugRule {
+    pub dev_sell_pct: f64,
+    pub within: Duration,
+}
+
+impl Default for RugRule {
+    fn default() -> Self {
+        Self {
+            dev_sell_pct: 0.5,
+            within: Duration::from_secs(30 * 60),
+        }
+    }
+}
+
+/// Track record of one creator wallet
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct CreatorStats {
+    pub tokens_launched: u32,
+    pub rugged: u32,
+    /// Launch to last observed trade, summed over all launches
+    pub total_lifetime_secs: f64,
+    pub our_trades: u32,
+    pub our_pnl_sol: f64,
+}
+
+impl CreatorStats {
+    pub fn rug_rate(&self) -> f64 {
+        if self.tokens_launched == 0 {
+            return 0.0;
+        }
+        self.rugged as f64 / self.tokens_launched as f64
+    }
+
+    pub fn avg_lifetime(&self) -> Duration {
+        if self.tokens_launched == 0 {
+            return Duration::ZERO;
+        }
+        Duration::from_secs_f64(self.total_lifetime_secs.max(0.0) / self.tokens_launched as f64)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Launch {
+    creator: String,
+    launched_at: DateTime<Utc>,
+    last_trade_at: DateTime<Utc>,
+    dev_bought: f64,
+    dev_sold: f64,
+    rugged: bool,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct ReputationState {
+    creators: HashMap<String, CreatorStats>,
+    launches: MintTable<Launch>,
+}
+
+/// Local record of creator wallets, fed from observed trades and our own
+/// closed trades
+pub struct ReputationStore {
+    rug_rule: RugRule,
+    state: Mutex<ReputationState>,
+}
+
+impl ReputationStore {
+    pub fn new(rug_rule: RugRule) -> Self {
+        Self {
+            rug_rule,
+            state: Mutex::new(ReputationState::default()),
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, ReputationState> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Update from one create, buy or sell event
+    pub fn observe(&self, event: &TradeEvent) {
+        let mut state = self.lock();
+        if event.kind == TradeKind::Create {
+            if state.launches.contains_key(&event.mint_address) {
+                return;
+            }
+            if !state.launches.has_room_for(&event.mint_address) {
+                self.prune(&mut state, &event.mint_address);
+            }
+            state.launches.insert(
+                event.mint_address.clone(),
+                Launch {
+                    creator: event.trader.clone(),
+                    launched_at: event.timestamp,
+                    last_trade_at: event.timestamp,
+                    dev_bought: event.token_amount,
+                    dev_sold: 0.0,
+                    rugged: false,
+                },
+            );
+            state
+                .creators
+                .entry(event.trader.clone())
+                .or_default()
+                .tokens_launched += 1;
+            return;
+        }
+
+        let ReputationState { creators, launches } = &mut *state;
+        let Some(launch) = launches.get_mut(&event.mint_address) else {
+            return;
+        };
+        let stats = creators.entry(launch.creator.clone()).or_default();
+        if event.timestamp > launch.last_trade_at {
+            stats.total_lifetime_secs +=
+                (event.timestamp - launch.last_trade_at).num_milliseconds() as f64 / 1000.0;
+            launch.last_trade_at = event.timestamp;
+        }
+        if event.trader != launch.creator {
+            return;
+        }
+        match event.kind {
+            TradeKind::Buy => launch.dev_bought += event.token_amount,
+            TradeKind::Sell => launch.dev_sold += event.token_amount,
+            TradeKind::Create => {}
+        }
+
+        let since_launch = (event.timestamp - launch.launched_at)
+            .to_std()
+            .unwrap_or_default();
+        let sold_pct = if launch.dev_bought > 0.0 {
+            launch.dev_sold / launch.dev_bought
+        } else {
+            0.0
+        };
+        if !launch.rugged
+            && event.kind == TradeKind::Sell
+            && since_launch <= self.rug_rule.within
+            && sold_pct >= self.rug_rule.dev_sell_pct
+        {
+            launch.rugged = true;
+            stats.rugged += 1;
+            warn!(
+                mint = %event.mint_address,
+                creator = %launch.creator,
+                sold_pct,
+                since_launch_secs = since_launch.as_secs(),
+                "Creator dumped their tokens"
+            );
+        }
+    }
+
+    /// Drop launches whose rug window has passed, or all of them if that
+    /// is not enough. Creator stats are kept, but lifetimes of dropped
+    /// launches stop growing.
+    fn prune(&self, state: &mut ReputationState, mint_address: &str) {
+        let newest = state
+            .launches
+            .values()
+            .map(|l| l.launched_at)
+            .max()
+            .unwrap_or_else(Utc::now);
+        let cutoff = newest - chrono::Duration::from_std(self.rug_rule.within).unwrap_or_default();
+        state
+            .launches
+            .make_room(mint_address, |l| l.launched_at > cutoff);
+    }
+
+    /// Attribute our result on a mint to its creator
+    pub fn record_trade(&self, trade: &CompletedTrade) {
+        let mut state = self.lock();
+        let Some(creator) = state
+            .launches
+            .get(&trade.mint_address)
+            .map(|l| l.creator.clone())
+        else {
+            return;
+        };
+        let stats = state.creators.entry(creator).or_default();
+        stats.our_trades += 1;
+        stats.our_pnl_sol += trade.pnl_sol;
+    }
+
+    pub fn stats(&self, creator: &str) -> Option<CreatorStats> {
+        self.lock().creators.get(creator).cloned()
+    }
+
+    pub fn creator_of(&self, mint_address: &str) -> Option<String> {
+        self.lock()
+            .launches
+            .get(mint_address)
+            .map(|l| l.creator.clone())
+    }
+
+    /// Write the store to `path` as JSON
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<()> {
+        let path = path.as_ref();
+        let json = serde_json::to_vec(&*self.lock()).context("Failed to serialize reputation")?;
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let tmp = path.with_extension("tmp");
+        std::fs::write(&tmp, json).with_context(|| format!("Failed to write {}", tmp.display()))?;
+        std::fs::rename(&tmp, path)
+            .with_context(|| format!("Failed to move reputation into {}", path.display()))?;
+        Ok(())
+    }
+
+    /// Restore a store written by `save`. A missing file is not an error.
+    pub fn load(&self, path: impl AsRef<Path>) -> Result<bool> {
+        let path = path.as_ref();
+        if !path.exists() {
+            return Ok(false);
+        }
+        let json =
+            std::fs::read(path).with_context(|| format!("Failed to read {}", path.display()))?;
+        let restored: ReputationState =
+            serde_json::from_slice(&json).context("Failed to deserialize reputation")?;
+        info!(
+            creators = restored.creators.len(),
+            path = %path.display(),
+            "Creator reputation restored"
+        );
+        *self.lock() = restored;
+        Ok(true)
+    }
+}
+
+/// Limits a creator must stay within. Rates and averages only apply once
+/// the creator has `min_launches` tokens behind them.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReputationThresholds {
+    pub min_launches: u32,
+    pub max_rug_rate: f64,
+    pub max_launches: Option<u32>,
+    pub min_avg_lifetime: Option<Duration>,
+    /// Our realized PnL on the creator's tokens must stay above this
+    pub min_our_pnl_sol: Option<f64>,
+}
+
+impl Default for ReputationThresholds {
+    fn default() -> Self {
+        Self {
+            min_launches: 2,
+            max_rug_rate: 0.5,
+            max_launches: None,
+            min_avg_lifetime: None,
+            min_our_pnl_sol: None,
+        }
+    }
+}
+
+/// Rejects tokens from denylisted creators or creators with a bad record.
+/// Allowlisted creators always pass; unknown creators pass unless
+/// `require_creator` is set.
+pub struct CreatorReputationFilter {
+    store: Arc<ReputationStore>,
+    thresholds: ReputationThresholds,
+    allowlist: HashSet<String>,
+    denylist: HashSet<String>,
+    require_creator: bool,
+}
+
+impl CreatorReputationFilter {
+    pub fn new(store: Arc<ReputationStore>, thresholds: ReputationThresholds) -> Self {
+        Self {
+            store,
+            thresholds,
+            allowlist: HashSet::new(),
+            denylist: HashSet::new(),
+            require_creator: false,
+        }
+    }
+
+    pub fn with_allowlist(mut self, wallets: impl IntoIterator<Item = String>) -> Self {
+        self.allowlist.extend(wallets);
+        self
+    }
+
+    pub fn with_denylist(mut self, wallets: impl IntoIterator<Item = String>) -> Self {
+        self.denylist.extend(wallets);
+        self
+    }
+
+    pub fn with_allowlist_file(self, path: impl AsRef<Path>) -> Result<Self> {
+        let wallets = read_wallet_list(path.as_ref())?;
+        Ok(self.with_allowlist(wallets))
+    }
+
+    pub fn with_denylist_file(self, path: impl AsRef<Path>) -> Result<Self> {
+        let wallets = read_wallet_list(path.as_ref())?;
+        Ok(self.with_denylist(wallets))
+    }
+
+    pub fn with_require_creator(mut self) -> Self {
+        self.require_creator = true;
+        self
+    }
+}
+
+/// One wallet per line, blank lines and `#` comments ignored
+fn read_wallet_list(path: &Path) -> Result<Vec<String>> {
+    let text = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read wallet list {}", path.display()))?;
+    let wallets: Vec<String> = text
+        .lines()
+        .map(|l| l.split('#').next().unwrap_or("").trim())
+        .filter(|l| !l.is_empty())
+        .map(str::to_string)
+        .collect();
+    info!(wallets = wallets.len(), path = %path.display(), "Wallet list loaded");
+    Ok(wallets)
+}
+
+impl Filter for CreatorReputationFilter {
+    fn name(&self) -> &str {
+        "creator_reputation"
+    }
+
+    fn evaluate(&self, signal: &MintSignal) -> Decision {
+        let creator = signal
+            .creator
+            .clone()
+            .or_else(|| self.store.creator_of(&signal.mint_address));
+        let Some(creator) = creator else {
+            if self.require_creator {
+                return Decision::reject(self.name(), "unknown_creator", f64::NAN, f64::NAN);
+            }
+            return Decision::Pass;
+        };
+        if self.allowlist.contains(&creator) {
+            return Decision::Pass;
+        }
+        if self.denylist.contains(&creator) {
+            return Decision::reject(self.name(), "denylisted", f64::NAN, f64::NAN);
+        }
+        let Some(stats) = self.store.stats(&creator) else {
+            return Decision::Pass;
+        };
+
+        let t = &self.thresholds;
+        if let Some(max) = t.max_launches.filter(|max| stats.tokens_launched > *max) {
+            return Decision::reject(
+                self.name(),
+                "serial_deployer",
+                stats.tokens_launched as f64,
+                max as f64,
+            );
+        }
+        if let Some(min) = t.min_our_pnl_sol.filter(|min| stats.our_pnl_sol < *min) {
+            return Decision::reject(self.name(), "losing_creator", stats.our_pnl_sol, min);
+        }
+        if stats.tokens_launched < t.min_launches {
+            return Decision::Pass;
+        }
+        if stats.rug_rate() > t.max_rug_rate {
+            return Decision::reject(self.name(), "rug_rate", stats.rug_rate(), t.max_rug_rate);
+        }
+        if let Some(min) = t.min_avg_lifetime.filter(|min| stats.avg_lifetime() < *min) {
+            return Decision::reject(
+                self.name(),
+                "short_lived",
+                stats.avg_lifetime().as_secs_f64(),
+                min.as_secs_f64(),
+            );
+        }
+        Decision::Pass
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::at;
+
+    fn event(mint: &str, trader: &str, kind: TradeKind, tokens: f64, secs: i64) -> TradeEvent {
+        let mut event = TradeEvent::new(mint.to_string(), trader.to_string(), kind, 1.0, tokens);
+        event.timestamp = at(secs);
+        event
+    }
+
+    fn signal(mint: &str, creator: &str) -> MintSignal {
+        MintSignal::new(
+            mint.to_string(),
+            10_000.0,
+            5_000.0,
+            0.001,
+            100,
+            5_000.0,
+            10.0,
+        )
+        .with_creator(creator.to_string())
+    }
+
+    /// `dev` launches `n` tokens and dumps every one within a minute
+    fn serial_rugger(store: &ReputationStore, dev: &str, n: usize) {
+        for i in 0..n {
+            let mint = format!("{dev}_{i}");
+            store.observe(&event(&mint, dev, TradeKind::Create, 1_000.0, 0));
+            store.observe(&event(&mint, "buyer", TradeKind::Buy, 500.0, 10));
+            store.observe(&event(&mint, dev, TradeKind::Sell, 900.0, 60));
+        }
+    }
+
+    #[test]
+    fn test_store_tracks_rugs_and_lifetime() {
+        let store = ReputationStore::new(RugRule::default());
+        serial_rugger(&store, "dev", 2);
+        // A clean launch that trades for an hour
+        store.observe(&event("clean", "dev", TradeKind::Create, 1_000.0, 0));
+        store.observe(&event("clean", "dev", TradeKind::Sell, 100.0, 60));
+        store.observe(&event("clean", "buyer", TradeKind::Buy, 10.0, 3_600));
+
+        let stats = store.stats("dev").unwrap();
+        assert_eq!(stats.tokens_launched, 3);
+        assert_eq!(stats.rugged, 2);
+        assert_eq!(
+            stats.avg_lifetime(),
+            Duration::from_secs((60 + 60 + 3_600) / 3)
+        );
+        assert_eq!(store.stats("buyer"), None);
+    }
+
+    #[test]
+    fn test_late_dev_sell_is_not_a_rug() {
+        let store = ReputationStore::new(RugRule::default());
+        store.observe(&event("m", "dev", TradeKind::Create, 1_000.0, 0));
+        store.observe(&event("m", "dev", TradeKind::Sell, 1_000.0, 7_200));
+        assert_eq!(store.stats("dev").unwrap().rugged, 0);
+    }
+
+    #[test]
+    fn test_filter_thresholds_and_lists() {
+        let store = Arc::new(ReputationStore::new(RugRule::default()));
+        serial_rugger(&store, "rugger", 3);
+        let filter = CreatorReputationFilter::new(store.clone(), ReputationThresholds::default());
+
+        assert!(matches!(
+            filter.evaluate(&signal("new", "rugger")),
+            Decision::Reject { reason, value, .. } if reason == "rug_rate" && value == 1.0
+        ));
+        assert!(filter.evaluate(&signal("new", "stranger")).is_pass());
+
+        let listed = CreatorReputationFilter::new(store, ReputationThresholds::default())
+            .with_allowlist(["rugger".to_string()])
+            .with_denylist(["stranger".to_string()]);
+        assert!(listed.evaluate(&signal("new", "rugger")).is_pass());
+        assert!(matches!(
+            listed.evaluate(&signal("new", "stranger")),
+            Decision::Reject { reason, .. } if reason == "denylisted"
+        ));
+    }
+
+    #[test]
+    fn test_our_pnl_and_persistence() {
+        let store = Arc::new(ReputationStore::new(RugRule::default()));
+        store.observe(&event("m", "dev", TradeKind::Create, 1_000.0, 0));
+        store.record_trade(&CompletedTrade {
+            position_id: "p".to_string(),
+            mint_address: "m".to_string(),
+            entry_price_usd: 1.0,
+            exit_price_usd: 0.5,
+            size_sol: 1.0,
+            pnl_sol: -0.5,
+            opened_at: Utc::now(),
+            closed_at: Utc::now(),
+            exit_reason: "stop_loss".to_string(),
+            strategy_id: None,
+        });
+        let thresholds = ReputationThresholds {
+            min_our_pnl_sol: Some(-0.2),
+            ..Default::default()
+        };
+        let filter = CreatorReputationFilter::new(store.clone(), thresholds);
+        // Creator looked up from the store when the signal has none
+        let mut unsigned = signal("m", "dev");
+        unsigned.creator = None;
+        assert!(matches!(
+            filter.evaluate(&unsigned),
+            Decision::Reject { reason, .. } if reason == "losing_creator"
+        ));
+
+        let path =
+            std::env::temp_dir().join(format!("hydra_reputation_{}.json", std::process::id()));
+        store.save(&path).unwrap();
+        let restored = ReputationStore::new(RugRule::default());
+        assert!(restored.load(&path).unwrap());
+        assert_eq!(restored.stats("dev"), store.stats("dev"));
+        std::fs::remove_file(&path).ok();
+    }
+}
@@ -0,0 +1,672 @@
+use anyhow::{Context, Result};
+use hydra_core::signal::{FlowFeatures, MintSignal};
+use serde::Deserialize;
+use std::path::Path;
+use tracing::info;
+
+use crate::error::StrategyError;
+use crate::filters::{Decision, Filter};
+
+/// Numeric value a rule can read from a signal
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Field {
+    MarketCapUsd,
+    Volume24hUsd,
+    PriceUsd,
+    HolderCount,
+    LiquidityUsd,
+    TopHolderPct,
+    /// `volume_24h_usd / market_cap_usd`, 0 when mcap is 0
+    VolumeToMcap,
+    /// `liquidity_usd / market_cap_usd`, 0 when mcap is 0
+    LiquidityToMcap,
+    /// Social links in resolved metadata, 0 without metadata
+    SocialCount,
+    /// Order-flow features, 0 when the signal has no `flow`
+    BuysPerSec,
+    SellsPerSec,
+    UniqueBuyers,
+    BuySellRatio,
+    HolderGrowthPerMin,
+    PriceVelocity,
+    PriceAcceleration,
+    AvgBuySol,
+}
+
+/// Boolean value a rule can read from a signal
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Flag {
+    HasMetadata,
+    HasUri,
+    HasFlow,
+}
+
+impl Field {
+    fn from_name(name: &str) -> Option<Self> {
+        Some(match name {
+            "market_cap_usd" => Self::MarketCapUsd,
+            "volume_24h_usd" => Self::Volume24hUsd,
+            "price_usd" => Self::PriceUsd,
+            "holder_count" => Self::HolderCount,
+            "liquidity_usd" => Self::LiquidityUsd,
+            "top_holder_pct" => Self::TopHolderPct,
+            "volume_to_mcap" => Self::VolumeToMcap,
+            "liquidity_to_mcap" => Self::LiquidityToMcap,
+            "social_count" => Self::SocialCount,
+            "buys_per_sec" => Self::BuysPerSec,
+            "sells_per_sec" => Self::SellsPerSec,
+            "unique_buyers" => Self::UniqueBuyers,
+            "buy_sell_ratio" => Self::BuySellRatio,
+            "holder_growth_per_min" => Self::HolderGrowthPerMin,
+            "price_velocity" => Self::PriceVelocity,
+            "price_acceleration" => Self::PriceAcceleration,
+            "avg_buy_sol" => Self::AvgBuySol,
+            _ => return None,
+        })
+    }
+
+    fn value(self, signal: &MintSignal) -> f64 {
+        let per_mcap = |v: f64| {
+            if signal.market_cap_usd > 0.0 {
+                v / signal.market_cap_usd
+            } else {
+                0.0
+            }
+        };
+        let flow = |f: fn(&FlowFeatures) -> f64| signal.flow.as_ref().map_or(0.0, f);
+        match self {
+            Self::MarketCapUsd => signal.market_cap_usd,
+            Self::Volume24hUsd => signal.volume_24h_usd,
+            Self::PriceUsd => signal.price_usd,
+            Self::HolderCount => signal.holder_count as f64,
+            Self::LiquidityUsd => signal.liquidity_usd,
+            Self::TopHolderPct => signal.top_holder_pct,
+            Self::VolumeToMcap => per_mcap(signal.volume_24h_usd),
+            Self::LiquidityToMcap => per_mcap(signal.liquidity_usd),
+            Self::SocialCount => signal
+                .metadata
+                .as_ref()
+                .map_or(0.0, |m| m.social_count() as f64),
+            Self::BuysPerSec => flow(|f| f.buys_per_sec),
+            Self::SellsPerSec => flow(|f| f.sells_per_sec),
+            Self::UniqueBuyers => flow(|f| f.unique_buyers as f64),
+            Self::BuySellRatio => flow(|f| f.buy_sell_volume_ratio),
+            Self::HolderGrowthPerMin => flow(|f| f.holder_growth_per_min),
+            Self::PriceVelocity => flow(|f| f.price_velocity),
+            Self::PriceAcceleration => flow(|f| f.price_acceleration),
+            Self::AvgBuySol => flow(|f| f.avg_buy_sol),
+        }
+    }
+}
+
+impl Flag {
+    fn from_name(name: &str) -> Option<Self> {
+        Some(match name {
+            "has_metadata" => Self::HasMetadata,
+            "has_uri" => Self::HasUri,
+            "has_flow" => Self::HasFlow,
+            _ => return None,
+        })
+    }
+
+    fn value(self, signal: &MintSignal) -> bool {
+        match self {
+            Self::HasMetadata => signal.metadata.is_some(),
+            Self::HasUri => signal.uri.is_some(),
+            Self::HasFlow => signal.flow.is_some(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CmpOp {
+    Lt,
+    Le,
+    Gt,
+    Ge,
+    Eq,
+    Ne,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ArithOp {
+    Add,
+    Sub,
+    Mul,
+    Div,
+}
+
+/// Compiled expression. Types are checked at compile time so evaluation
+/// never has to.
+#[derive(Debug, Clone)]
+enum Expr {
+    Num(f64),
+    Field(Field),
+    Neg(Box<Expr>),
+    Arith(ArithOp, Box<Expr>, Box<Expr>),
+    Bool(bool),
+    Flag(Flag),
+    Cmp(CmpOp, Box<Expr>, Box<Expr>),
+    Not(Box<Expr>),
+    And(Vec<Expr>),
+    Or(Vec<Expr>),
+}
+
+impl Expr {
+    fn is_bool(&self) -> bool {
+        matches!(
+            self,
+            Self::Bool(_)
+                | Self::Flag(_)
+                | Self::Cmp(..)
+                | Self::Not(_)
+                | Self::And(_)
+                | Self::Or(_)
+        )
+    }
+
+    fn num(&self, signal: &MintSignal) -> f64 {
+        match self {
+            Self::Num(v) => *v,
+            Self::Field(field) => field.value(signal),
+            Self::Neg(inner) => -inner.num(signal),
+            Self::Arith(op, lhs, rhs) => {
+                let (l, r) = (lhs.num(signal), rhs.num(signal));
+                match op {
+                    ArithOp::Add => l + r,
+                    ArithOp::Sub => l - r,
+                    ArithOp::Mul => l * r,
+                    ArithOp::Div => l / r,
+                }
+            }
+            _ => f64::NAN,
+        }
+    }
+
+    fn truth(&self, signal: &MintSignal) -> bool {
+        match self {
+            Self::Bool(v) => *v,
+            Self::Flag(flag) => flag.value(signal),
+            Self::Cmp(op, lhs, rhs) => {
+                let (l, r) = (lhs.num(signal), rhs.num(signal));
+                match op {
+                    CmpOp::Lt => l < r,
+                    CmpOp::Le => l <= r,
+                    CmpOp::Gt => l > r,
+                    CmpOp::Ge => l >= r,
+                    CmpOp::Eq => l == r,
+                    CmpOp::Ne => l != r,
+                }
+            }
+            Self::Not(inner) => !inner.truth(signal),
+            Self::And(terms) => terms.iter().all(|t| t.truth(signal)),
+            Self::Or(terms) => terms.iter().any(|t| t.truth(signal)),
+            _ => false,
+        }
+    }
+
+    /// Operands of the comparison that made a conjunction fail, if the
+    /// failure comes down to a single comparison
+    fn failing_comparison(&self, signal: &MintSignal) -> Option<(f64, f64)> {
+        match self {
+            Self::Cmp(_, lhs, rhs) => Some((lhs.num(signal), rhs.num(signal))),
+            Self::And(terms) => terms
+                .iter()
+                .find(|t| !t.truth(signal))?
+                .failing_comparison(signal),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Num(f64),
+    Ident(String),
+    LParen,
+    RParen,
+    And,
+    Or,
+    Not,
+    Cmp(CmpOp),
+    Arith(ArithOp),
+}
+
+struct Parser<'a> {
+    rule: &'a str,
+    tokens: Vec<(Token, usize)>,
+    pos: usize,
+    end: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn new(rule: &'a str, source: &str) -> Result<Self, StrategyError> {
+        let chars: Vec<char> = source.chars().collect();
+        let mut tokens = Vec::new();
+        let mut i = 0;
+        while i < chars.len() {
+            let c = chars[i];
+            let column = i + 1;
+            if c.is_whitespace() {
+                i += 1;
+                continue;
+            }
+            let two: String = chars[i..(i + 2).min(chars.len())].iter().collect();
+            let (token, width) = match two.as_str() {
+                "&&" => (Token::And, 2),
+                "||" => (Token::Or, 2),
+                "<=" => (Token::Cmp(CmpOp::Le), 2),
+                ">=" => (Token::Cmp(CmpOp::Ge), 2),
+                "==" => (Token::Cmp(CmpOp::Eq), 2),
+                "!=" => (Token::Cmp(CmpOp::Ne), 2),
+                _ => match c {
+                    '(' => (Token::LParen, 1),
+                    ')' => (Token::RParen, 1),
+                    '!' => (Token::Not, 1),
+                    '<' => (Token::Cmp(CmpOp::Lt), 1),
+                    '>' => (Token::Cmp(CmpOp::Gt), 1),
+                    '+' => (Token::Arith(ArithOp::Add), 1),
+                    '-' => (Token::Arith(ArithOp::Sub), 1),
+                    '*' => (Token::Arith(ArithOp::Mul), 1),
+                    '/' => (Token::Arith(ArithOp::Div), 1),
+                    c if c.is_ascii_digit() || c == '.' => {
+                        let len = chars[i..]
+                            .iter()
+                            .take_while(|c| c.is_ascii_digit() || **c == '.' || **c == '_')
+                            .count();
+                        let text: String =
+                            chars[i..i + len].iter().filter(|c| **c != '_').collect();
+                        let value = text.parse::<f64>().map_err(|_| StrategyError::RuleParse {
+                            rule: rule.to_string(),
+                            column,
+                            message: format!("invalid number '{text}'"),
+                        })?;
+                        (Token::Num(value), len)
+                    }
+                    c if c.is_ascii_alphabetic() || c == '_' => {
+                        let len = chars[i..]
+                            .iter()
+                            .take_while(|c| c.is_ascii_alphanumeric() || **c == '_')
+                            .count();
+                        (Token::Ident(chars[i..i + len].iter().collect()), len)
+                    }
+                    other => {
+                        return Err(StrategyError::RuleParse {
+                            rule: rule.to_string(),
+                            column,
+                            message: format!("unexpected character '{other}'"),
+                        })
+                    }
+                },
+            };
+            tokens.push((token, column));
+            i += width;
+        }
+        Ok(Self {
+            rule,
+            tokens,
+            pos: 0,
+            end: chars.len() + 1,
+        })
+    }
+
+    fn parse(mut self) -> Result<Expr, StrategyError> {
+        let expr = self.or()?;
+        if self.pos < self.tokens.len() {
+            return Err(self.error("unexpected trailing input"));
+        }
+        if !expr.is_bool() {
+            return Err(self.type_error("rule must evaluate to true or false"));
+        }
+        Ok(expr)
+    }
+
+    fn or(&mut self) -> Result<Expr, StrategyError> {
+        let mut terms = vec![self.and()?];
+        while self.eat(&Token::Or) {
+            terms.push(self.and()?);
+        }
+        self.logical(terms, Expr::Or)
+    }
+
+    fn and(&mut self) -> Result<Expr, StrategyError> {
+        let mut terms = vec![self.not()?];
+        while self.eat(&Token::And) {
+            terms.push(self.not()?);
+        }
+        self.logical(terms, Expr::And)
+    }
+
+    fn logical(
+        &self,
+        mut terms: Vec<Expr>,
+        build: fn(Vec<Expr>) -> Expr,
+    ) -> Result<Expr, StrategyError> {
+        if terms.len() == 1 {
+            return Ok(terms.remove(0));
+        }
+        if terms.iter().any(|t| !t.is_bool()) {
+            return Err(self.type_error("'&&' and '||' need boolean operands"));
+        }
+        Ok(build(terms))
+    }
+
+    fn not(&mut self) -> Result<Expr, StrategyError> {
+        if self.eat(&Token::Not) {
+            let inner = self.not()?;
+            if !inner.is_bool() {
+                return Err(self.type_error("'!' needs a boolean operand"));
+            }
+            return Ok(Expr::Not(Box::new(inner)));
+        }
+        self.comparison()
+    }
+
+    fn comparison(&mut self) -> Result<Expr, StrategyError> {
+        let lhs = self.sum()?;
+        let Some((Token::Cmp(op), _)) = self.peek().cloned() else {
+            return Ok(lhs);
+        };
+        self.pos += 1;
+        let rhs = self.sum()?;
+        if lhs.is_bool() || rhs.is_bool() {
+            return Err(self.type_error("comparisons need numeric operands"));
+        }
+        Ok(Expr::Cmp(op, Box::new(lhs), Box::new(rhs)))
+    }
+
+    fn sum(&mut self) -> Result<Expr, StrategyError> {
+        let mut lhs = self.product()?;
+        while let Some((Token::Arith(op @ (ArithOp::Add | ArithOp::Sub)), _)) = self.peek().cloned()
+        {
+            self.pos += 1;
+            let rhs = self.product()?;
+            lhs = self.arith(op, lhs, rhs)?;
+        }
+        Ok(lhs)
+    }
+
+    fn product(&mut self) -> Result<Expr, StrategyError> {
+        let mut lhs = self.unary()?;
+        while let Some((Token::Arith(op @ (ArithOp::Mul | ArithOp::Div)), _)) = self.peek().cloned()
+        {
+            self.pos += 1;
+            let rhs = self.unary()?;
+            lhs = self.arith(op, lhs, rhs)?;
+        }
+        Ok(lhs)
+    }
+
+    fn arith(&self, op: ArithOp, lhs: Expr, rhs: Expr) -> Result<Expr, StrategyError> {
+        if lhs.is_bool() || rhs.is_bool() {
+            return Err(self.type_error("arithmetic needs numeric operands"));
+        }
+        Ok(Expr::Arith(op, Box::new(lhs), Box::new(rhs)))
+    }
+
+    fn unary(&mut self) -> Result<Expr, StrategyError> {
+        if self.eat(&Token::Arith(ArithOp::Sub)) {
+            let inner = self.unary()?;
+            if inner.is_bool() {
+                return Err(self.type_error("'-' needs a numeric operand"));
+            }
+            return Ok(Expr::Neg(Box::new(inner)));
+        }
+        self.primary()
+    }
+
+    fn primary(&mut self) -> Result<Expr, StrategyError> {
+        let Some((token, _)) = self.peek().cloned() else {
+            return Err(self.error("unexpected end of rule"));
+        };
+        match token {
+            Token::Num(value) => {
+                self.pos += 1;
+                Ok(Expr::Num(value))
+            }
+            Token::Ident(name) => {
+                let expr = match name.as_str() {
+                    "true" => Expr::Bool(true),
+                    "false" => Expr::Bool(false),
+                    _ => {
+                        if let Some(field) = Field::from_name(&name) {
+                            Expr::Field(field)
+                        } else if let Some(flag) = Flag::from_name(&name) {
+                            Expr::Flag(flag)
+                        } else {
+                            return Err(StrategyError::UnknownField {
+                                rule: self.rule.to_string(),
+                                field: name,
+                            });
+                        }
+                    }
+                };
+                self.pos += 1;
+                Ok(expr)
+            }
+            Token::LParen => {
+                self.pos += 1;
+                let inner = self.or()?;
+                if !self.eat(&Token::RParen) {
+                    return Err(self.error("expected ')'"));
+                }
+                Ok(inner)
+            }
+            _ => Err(self.error("expected a number, field or '('")),
+        }
+    }
+
+    fn peek(&self) -> Option<&(Token, usize)> {
+        self.tokens.get(self.pos)
+    }
+
+    fn eat(&mut self, token: &Token) -> bool {
+        if self.peek().is_some_and(|(t, _)| t == token) {
+            self.pos += 1;
+            return true;
+        }
+        false
+    }
+
+    fn error(&self, message: &str) -> StrategyError {
+        StrategyError::RuleParse {
+            rule: self.rule.to_string(),
+            column: self.peek().map_or(self.end, |(_, column)| *column),
+            message: message.to_string(),
+        }
+    }
+
+    fn type_error(&self, message: &str) -> StrategyError {
+        StrategyError::RuleType {
+            rule: self.rule.to_string(),
+            message: message.to_string(),
+        }
+    }
+}
+
+/// A named condition a signal must satisfy, e.g.
+/// `liquidity_usd > 5000 && (holder_count > 100 || volume_24h_usd > 20000)`
+#[derive(Debug, Clone)]
+pub struct Rule {
+    name: String,
+    source: String,
+    expr: Expr,
+}
+
+impl Rule {
+    pub fn compile(name: &str, source: &str) -> Result<Self, StrategyError> {
+        let expr = Parser::new(name, source)?.parse()?;
+        Ok(Self {
+            name: name.to_string(),
+            source: source.to_string(),
+            expr,
+        })
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+
+    pub fn matches(&self, signal: &MintSignal) -> bool {
+        self.expr.truth(signal)
+    }
+}
+
+/// One entry of a JSON rules file
+#[derive(Debug, Clone, Deserialize)]
+pub struct RuleConfig {
+    pub name: String,
+    pub expr: String,
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+/// Filter built from declarative rules. A signal is rejected by the first
+/// rule it does not satisfy, with the rule name as the rejection reason.
+pub struct RuleFilter {
+    rules: Vec<Rule>,
+}
+
+impl RuleFilter {
+    pub fn new(rules: Vec<Rule>) -> Self {
+        Self { rules }
+    }
+
+    pub fn from_configs(configs: &[RuleConfig]) -> Result<Self, StrategyError> {
+        let rules = configs
+            .iter()
+            .filter(|c| c.enabled)
+            .map(|c| Rule::compile(&c.name, &c.expr))
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Self::new(rules))
+    }
+
+    /// Load a JSON array of `{ "name": ..., "expr": ..., "enabled": ... }`
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let json = std::fs::read(path)
+            .with_context(|| format!("Failed to read rules file {}", path.display()))?;
+        let configs: Vec<RuleConfig> =
+            serde_json::from_slice(&json).context("Failed to parse rules file")?;
+        let filter = Self::from_configs(&configs)?;
+        info!(rules = filter.rules.len(), path = %path.display(), "Strategy rules loaded");
+        Ok(filter)
+    }
+
+    pub fn rules(&self) -> &[Rule] {
+        &self.rules
+    }
+}
+
+impl Filter for RuleFilter {
+    fn name(&self) -> &str {
+        "rules"
+    }
+
+    fn evaluate(&self, signal: &MintSignal) -> Decision {
+        let Some(rule) = self.rules.iter().find(|r| !r.matches(signal)) else {
+            return Decision::Pass;
+        };
+        // Report the failing comparison when there is a single one to blame
+        let (value, threshold) = rule
+            .expr
+            .failing_comparison(signal)
+            .unwrap_or((f64::NAN, f64::NAN));
+        Decision::reject(self.name(), &rule.name, value, threshold)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn signal(liquidity: f64, top_holder: f64, holders: u64, volume: f64) -> MintSignal {
+        MintSignal::new(
+            "m".to_string(),
+            50_000.0,
+            volume,
+            0.001,
+            holders,
+            liquidity,
+            top_holder,
+        )
+    }
+
+    const EXAMPLE: &str =
+        "liquidity_usd > 5000 && top_holder_pct < 25 && (holder_count > 100 || volume_24h_usd > 20_000)";
+
+    #[test]
+    fn test_example_rule() {
+        let rule = Rule::compile("example", EXAMPLE).unwrap();
+        assert!(rule.matches(&signal(6_000.0, 10.0, 150, 0.0)));
+        assert!(rule.matches(&signal(6_000.0, 10.0, 50, 30_000.0)));
+        assert!(!rule.matches(&signal(6_000.0, 10.0, 50, 10_000.0)));
+        assert!(!rule.matches(&signal(4_000.0, 10.0, 150, 0.0)));
+    }
+
+    #[test]
+    fn test_precedence_and_arithmetic() {
+        let rule = Rule::compile("r", "volume_24h_usd / market_cap_usd >= 0.5 + 0.1 * 2").unwrap();
+        assert!(rule.matches(&signal(0.0, 0.0, 0, 35_000.0)));
+        assert!(!rule.matches(&signal(0.0, 0.0, 0, 30_000.0)));
+        let rule = Rule::compile("r", "!has_metadata && -top_holder_pct > -25").unwrap();
+        assert!(rule.matches(&signal(0.0, 10.0, 0, 0.0)));
+    }
+
+    #[test]
+    fn test_flow_fields() {
+        let rule =
+            Rule::compile("r", "has_flow && buys_per_sec > 2 && buy_sell_ratio >= 1.5").unwrap();
+        assert!(!rule.matches(&signal(0.0, 0.0, 0, 0.0)));
+        let flowing = signal(0.0, 0.0, 0, 0.0).with_flow(FlowFeatures {
+            buys_per_sec: 3.0,
+            buy_sell_volume_ratio: 2.0,
+            ..FlowFeatures::default()
+        });
+        assert!(rule.matches(&flowing));
+    }
+
+    #[test]
+    fn test_compile_errors() {
+        assert!(matches!(
+            Rule::compile("r", "liquidity > 5"),
+            Err(StrategyError::UnknownField { field, .. }) if field == "liquidity"
+        ));
+        assert!(matches!(
+            Rule::compile("r", "holder_count + 1"),
+            Err(StrategyError::RuleType { .. })
+        ));
+        assert!(matches!(
+            Rule::compile("r", "(holder_count > 1"),
+            Err(StrategyError::RuleParse { column: 18, .. })
+        ));
+        assert!(matches!(
+            Rule::compile("r", "holder_count > 1 && 5"),
+            Err(StrategyError::RuleType { .. })
+        ));
+    }
+
+    #[test]
+    fn test_rejection_names_rule_and_comparison() {
+        let configs: Vec<RuleConfig> = serde_json::from_str(
+            r#"[{"name": "min_liquidity", "expr": "liquidity_usd > 5000 && holder_count > 10"},
+                {"name": "disabled", "expr": "false", "enabled": false}]"#,
+        )
+        .unwrap();
+        let filter = RuleFilter::from_configs(&configs).unwrap();
+        assert_eq!(filter.rules().len(), 1);
+        assert_eq!(
+            filter.evaluate(&signal(1_000.0, 0.0, 50, 0.0)),
+            Decision::reject("rules", "min_liquidity", 1_000.0, 5_000.0)
+        );
+        assert!(filter.evaluate(&signal(9_000.0, 0.0, 50, 0.0)).is_pass());
+    }
+}
@@ -0,0 +1,310 @@
+use hydra_core::position::{CompletedTrade, Position};
+use hydra_core::traits::ScoredSignal;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use tracing::{debug, info};
+
+use crate::chain::FilterChain;
+use crate::sizing::{PositionSizing, SizingContext};
+use crate::tpsl::{ExitPlan, TpSlCalculator, TpSlLevels};
+
+/// Capital and position slots reserved for one strategy
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Allocation {
+    pub capital_sol: f64,
+    pub max_positions: usize,
+}
+
+/// One named configuration: filters, exits, sizing and AI threshold
+pub struct Strategy {
+    pub id: String,
+    pub allocation: Allocation,
+    pub min_confidence: f64,
+    chain: FilterChain,
+    tpsl: TpSlCalculator,
+    sizing: PositionSizing,
+}
+
+impl Strategy {
+    pub fn new(
+        id: String,
+        allocation: Allocation,
+        chain: FilterChain,
+        tpsl: TpSlCalculator,
+        sizing: PositionSizing,
+    ) -> Self {
+        Self {
+            id,
+            allocation,
+            min_confidence: 0.0,
+            chain,
+            tpsl,
+            sizing,
+        }
+    }
+
+    pub fn with_min_confidence(mut self, min_confidence: f64) -> Self {
+        self.min_confidence = min_confidence;
+        self
+    }
+}
+
+/// Capital in use and results of one strategy
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct StrategyBook {
+    pub open_positions: usize,
+    pub deployed_sol: f64,
+    pub closed_trades: u32,
+    pub realized_pnl_sol: f64,
+}
+
+/// An entry one strategy wants to take. Its capital and slot are
+/// reserved until `StrategyRunner::release` or `on_close`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StrategyEntry {
+    pub strategy_id: String,
+    pub size_sol: f64,
+    pub levels: TpSlLevels,
+    pub plan: ExitPlan,
+}
+
+impl StrategyEntry {
+    /// Position for this entry, tagged with the strategy id
+    pub fn position(&self, id: String, mint_address: String, entry_price_usd: f64) -> Position {
+        Position::new(
+            id,
+            mint_address,
+            entry_price_usd,
+            self.size_sol,
+            self.levels.take_profit_pct,
+            self.levels.stop_loss_pct,
+        )
+        .with_strategy(self.strategy_id.clone())
+    }
+}
+
+/// Runs several strategies side by side on the same signals. Each one
+/// decides independently, so two strategies may enter the same mint.
+/// Allocations are fixed; realized PnL is reported, not reinvested.
+#[derive(Default)]
+pub struct StrategyRunner {
+    strategies: Vec<Strategy>,
+    books: Mutex<HashMap<String, StrategyBook>>,
+}
+
+impl StrategyRunner {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a strategy, replacing any earlier one with the same id
+    pub fn with_strategy(mut self, strategy: Strategy) -> Self {
+        self.strategies.retain(|s| s.id != strategy.id);
+        self.strategies.push(strategy);
+        self
+    }
+
+    pub fn strategies(&self) -> impl Iterator<Item = &Strategy> {
+        self.strategies.iter()
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<String, StrategyBook>> {
+        self.books.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Entries of every strategy that accepts the signal, with capital
+    /// and a slot reserved for each
+    pub fn evaluate(&self, scored: &ScoredSignal) -> Vec<StrategyEntry> {
+        let mint = &scored.signal.mint_address;
+        let mut books = self.lock();
+        let mut entries = Vec::new();
+
+        for strategy in &self.strategies {
+            if scored.score < strategy.min_confidence {
+                debug!(strategy = %strategy.id, mint = %mint, score = scored.score, "Below strategy threshold");
+                continue;
+            }
+            let book = books.entry(strategy.id.clone()).or_default();
+            if book.open_positions >= strategy.allocation.max_positions {
+                debug!(strategy = %strategy.id, mint = %mint, "Strategy has no free slot");
+                continue;
+            }
+            if !strategy.chain.passes(&scored.signal) {
+                continue;
+            }
+            let levels = strategy.tpsl.calculate(scored.score);
+            let available = (strategy.allocation.capital_sol - book.deployed_sol).max(0.0);
+            let ctx = SizingContext::new(available, scored.score, levels.stop_loss_pct);
+            let size_sol = match strategy.sizing.size_sol(&ctx) {
+                Ok(size) => size,
+                Err(e) => {
+                    debug!(strategy = %strategy.id, mint = %mint, error = %e, "Strategy cannot size entry");
+                    continue;
+                }
+            };
+
+            book.open_positions += 1;
+            book.deployed_sol += size_sol;
+            info!(
+                strategy = %strategy.id,
+                mint = %mint,
+                size_sol,
+                deployed_sol = book.deployed_sol,
+                "Strategy entry"
+            );
+            entries.push(StrategyEntry {
+                strategy_id: strategy.id.clone(),
+                size_sol,
+                plan: strategy.tpsl.exit_plan(scored.score),
+                levels,
+            });
+        }
+        entries
+    }
+
+    /// Give back the reservation of an entry that was not filled
+    pub fn release(&self, entry: &StrategyEntry) {
+        if let Some(book) = self.lock().get_mut(&entry.strategy_id) {
+            book.open_positions = book.open_positions.saturating_sub(1);
+            book.deployed_sol = (book.deployed_sol - entry.size_sol).max(0.0);
+        }
+    }
+
+    /// Free the capital of a closed trade and book its PnL. Trades
+    /// without a strategy id are ignored.
+    pub fn on_close(&self, trade: &CompletedTrade) {
+        let Some(id) = &trade.strategy_id else {
+            return;
+        };
+        if let Some(book) = self.lock().get_mut(id) {
+            book.open_positions = book.open_positions.saturating_sub(1);
+            book.deployed_sol = (book.deployed_sol - trade.size_sol).max(0.0);
+            book.closed_trades += 1;
+            book.realized_pnl_sol += trade.pnl_sol;
+        }
+    }
+
+    pub fn book(&self, strategy_id: &str) -> Option<StrategyBook> {
+        self.lock().get(strategy_id).cloned()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chain::ChainMode;
+    use crate::filters::McapFilter;
+    use crate::sizing::FixedSolSizer;
+    use chrono::Utc;
+    use hydra_core::signal::MintSignal;
+
+    fn scored(mcap: f64, score: f64) -> ScoredSignal {
+        ScoredSignal {
+            signal: MintSignal::new("m".to_string(), mcap, 0.0, 0.001, 100, 5_000.0, 10.0),
+            score,
+            should_buy: true,
+        }
+    }
+
+    /// Conservative: high threshold, tight mcap band, 1 SOL in 2 slots.
+    /// Aggressive: any mcap, 3 SOL in 5 slots.
+    fn runner() -> StrategyRunner {
+        StrategyRunner::new()
+            .with_strategy(
+                Strategy::new(
+                    "conservative".to_string(),
+                    Allocation {
+                        capital_sol: 1.0,
+                        max_positions: 2,
+                    },
+                    FilterChain::new(ChainMode::ShortCircuit)
+                        .with_filter(McapFilter::new(5_000.0, 50_000.0)),
+                    TpSlCalculator::new(0.3, 0.1),
+                    PositionSizing::new(FixedSolSizer { size_sol: 0.5 }, 0.1, 1.0),
+                )
+                .with_min_confidence(0.8),
+            )
+            .with_strategy(Strategy::new(
+                "aggressive".to_string(),
+                Allocation {
+                    capital_sol: 3.0,
+                    max_positions: 5,
+                },
+                FilterChain::new(ChainMode::ShortCircuit),
+                TpSlCalculator::new(1.0, 0.3),
+                PositionSizing::new(FixedSolSizer { size_sol: 2.0 }, 0.1, 2.0),
+            ))
+    }
+
+    fn ids(entries: &[StrategyEntry]) -> Vec<&str> {
+        entries.iter().map(|e| e.strategy_id.as_str()).collect()
+    }
+
+    #[test]
+    fn test_each_strategy_decides_independently() {
+        assert_eq!(
+            ids(&runner().evaluate(&scored(10_000.0, 0.9))),
+            ["conservative", "aggressive"]
+        );
+        assert_eq!(
+            ids(&runner().evaluate(&scored(10_000.0, 0.7))),
+            ["aggressive"]
+        );
+        assert_eq!(
+            ids(&runner().evaluate(&scored(90_000.0, 0.9))),
+            ["aggressive"]
+        );
+
+        let runner = runner();
+        let entry = &runner.evaluate(&scored(10_000.0, 0.9))[0];
+        let position = entry.position("p1".to_string(), "m".to_string(), 0.001);
+        assert_eq!(position.strategy_id.as_deref(), Some("conservative"));
+        assert_eq!(position.take_profit_pct, entry.levels.take_profit_pct);
+    }
+
+    #[test]
+    fn test_allocation_limits_and_release() {
+        let runner = runner();
+        // Aggressive: 2 SOL, then only 1 SOL of its 3 left
+        let first = runner.evaluate(&scored(90_000.0, 0.5));
+        assert_eq!(first[0].size_sol, 2.0);
+        let second = runner.evaluate(&scored(90_000.0, 0.5));
+        assert_eq!(second[0].size_sol, 1.0);
+        assert!(runner.evaluate(&scored(90_000.0, 0.5)).is_empty());
+
+        runner.release(&second[0]);
+        let book = runner.book("aggressive").unwrap();
+        assert_eq!(book.open_positions, 1);
+        assert_eq!(book.deployed_sol, 2.0);
+    }
+
+    #[test]
+    fn test_close_frees_capital_and_books_pnl() {
+        let runner = runner();
+        runner.evaluate(&scored(10_000.0, 0.9));
+        runner.evaluate(&scored(10_000.0, 0.9));
+        // Both conservative slots taken
+        assert!(!ids(&runner.evaluate(&scored(10_000.0, 0.9))).contains(&"conservative"));
+
+        runner.on_close(&CompletedTrade {
+            position_id: "p1".to_string(),
+            mint_address: "m".to_string(),
+            entry_price_usd: 1.0,
+            exit_price_usd: 1.3,
+            size_sol: 0.5,
+            pnl_sol: 0.15,
+            opened_at: Utc::now(),
+            closed_at: Utc::now(),
+            exit_reason: "take_profit".to_string(),
+            strategy_id: Some("conservative".to_string()),
+        });
+        let book = runner.book("conservative").unwrap();
+        assert_eq!(book.open_positions, 1);
+        assert_eq!(book.closed_trades, 1);
+        assert!((book.realized_pnl_sol - 0.15).abs() < 1e-10);
+        assert_eq!(
+            ids(&runner.evaluate(&scored(10_000.0, 0.9)))[0],
+            "conservative"
+        );
+    }
+}
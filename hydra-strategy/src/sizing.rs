@@ -0,0 +1,327 @@
+use hydra_core::position::CompletedTrade;
+use tracing::debug;
+
+use crate::error::StrategyError;
+use crate::impact::{BondingCurve, ImpactLimit, ImpactSizedOrder};
+
+/// Everything a sizer may look at when sizing one entry
+#[derive(Debug, Clone, PartialEq)]
+pub struct SizingContext {
+    pub wallet_balance_sol: f64,
+    pub ai_confidence: f64,
+    /// Stop distance of the planned exit, used to turn the loss budget
+    /// into a size limit
+    pub stop_loss_pct: f64,
+    /// From `DailyLimits::remaining_loss_budget_sol`, `None` for no limit
+    pub remaining_loss_budget_sol: Option<f64>,
+}
+
+impl SizingContext {
+    pub fn new(wallet_balance_sol: f64, ai_confidence: f64, stop_loss_pct: f64) -> Self {
+        Self {
+            wallet_balance_sol,
+            ai_confidence,
+            stop_loss_pct,
+            remaining_loss_budget_sol: None,
+        }
+    }
+
+    pub fn with_loss_budget(mut self, remaining_loss_budget_sol: f64) -> Self {
+        self.remaining_loss_budget_sol = Some(remaining_loss_budget_sol);
+        self
+    }
+}
+
+/// Proposes a position size before caps are applied
+pub trait PositionSizer: Send + Sync {
+    fn name(&self) -> &str;
+    fn raw_size_sol(&self, ctx: &SizingContext) -> f64;
+}
+
+/// Always the same amount
+pub struct FixedSolSizer {
+    pub size_sol: f64,
+}
+
+impl PositionSizer for FixedSolSizer {
+    fn name(&self) -> &str {
+        "fixed_sol"
+    }
+
+    fn raw_size_sol(&self, _ctx: &SizingContext) -> f64 {
+        self.size_sol
+    }
+}
+
+/// A fixed fraction of the wallet balance
+pub struct FixedFractionSizer {
+    pub fraction: f64,
+}
+
+impl PositionSizer for FixedFractionSizer {
+    fn name(&self) -> &str {
+        "fixed_fraction"
+    }
+
+    fn raw_size_sol(&self, ctx: &SizingContext) -> f64 {
+        ctx.wallet_balance_sol * self.fraction
+    }
+}
+
+/// Win rate and payoff ratio of past trades
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct KellyStats {
+    pub win_rate: f64,
+    /// Average win divided by average loss
+    pub payoff_ratio: f64,
+}
+
+impl KellyStats {
+    /// `None` until there is at least one win and one loss
+    pub fn from_trades(trades: &[CompletedTrade]) -> Option<Self> {
+        let wins: Vec<f64> = trades
+            .iter()
+            .map(|t| t.pnl_sol)
+            .filter(|p| *p > 0.0)
+            .collect();
+        let losses: Vec<f64> = trades
+            .iter()
+            .map(|t| -t.pnl_sol)
+            .filter(|p| *p > 0.0)
+            .collect();
+        if wins.is_empty() || losses.is_empty() {
+            return None;
+        }
+        let avg_win = wins.iter().sum::<f64>() / wins.len() as f64;
+        let avg_loss = losses.iter().sum::<f64>() / losses.len() as f64;
+        Some(Self {
+            win_rate: wins.len() as f64 / (wins.len() + losses.len()) as f64,
+            payoff_ratio: avg_win / avg_loss,
+        })
+    }
+
+    /// Full Kelly fraction `p - (1 - p) / b`, never negative
+    pub fn kelly_fraction(&self) -> f64 {
+        if self.payoff_ratio <= 0.0 {
+            return 0.0;
+        }
+        (self.win_rate - (1.0 - self.win_rate) / self.payoff_ratio).max(0.0)
+    }
+}
+
+/// A fraction (e.g. 0.25 for quarter Kelly) of the Kelly-optimal bet
+pub struct FractionalKellySizer {
+    pub stats: KellyStats,
+    pub kelly_multiplier: f64,
+}
+
+impl PositionSizer for FractionalKellySizer {
+    fn name(&self) -> &str {
+        "fractional_kelly"
+    }
+
+    fn raw_size_sol(&self, ctx: &SizingContext) -> f64 {
+        ctx.wallet_balance_sol * self.stats.kelly_fraction() * self.kelly_multiplier
+    }
+}
+
+/// `base_sol` at `min_confidence`, rising linearly to
+/// `base_sol * max_multiplier` at confidence 1. Nothing below `min_confidence`.
+pub struct ConfidenceScaledSizer {
+    pub base_sol: f64,
+    pub min_confidence: f64,
+    pub max_multiplier: f64,
+}
+
+impl PositionSizer for ConfidenceScaledSizer {
+    fn name(&self) -> &str {
+        "confidence_scaled"
+    }
+
+    fn raw_size_sol(&self, ctx: &SizingContext) -> f64 {
+        let confidence = ctx.ai_confidence.clamp(0.0, 1.0);
+        if confidence < self.min_confidence {
+            return 0.0;
+        }
+        let span = (1.0 - self.min_confidence).max(f64::EPSILON);
+        let t = (confidence - self.min_confidence) / span;
+        self.base_sol * (1.0 + t * (self.max_multiplier - 1.0))
+    }
+}
+
+/// Wraps a sizer with hard limits: never below `min_sol`, never above
+/// `max_sol` or the wallet balance, and never more than the remaining
+/// daily loss budget would cover if the stop is hit.
+pub struct PositionSizing {
+    sizer: Box<dyn PositionSizer>,
+    min_sol: f64,
+    max_sol: f64,
+}
+
+impl PositionSizing {
+    pub fn new(sizer: impl PositionSizer + 'static, min_sol: f64, max_sol: f64) -> Self {
+        Self {
+            sizer: Box::new(sizer),
+            min_sol,
+            max_sol,
+        }
+    }
+
+    /// Size in SOL ready for `Position::new`, or `PositionTooSmall` when
+    /// the capped size would fall below the minimum.
+    pub fn size_sol(&self, ctx: &SizingContext) -> Result<f64, StrategyError> {
+        let raw = self.sizer.raw_size_sol(ctx);
+        let mut size = raw;
+        let mut limited_by = self.sizer.name().to_string();
+
+        let mut cap = |limit: f64, name: &str| {
+            if limit < size {
+                size = limit;
+                limited_by = name.to_string();
+            }
+        };
+        cap(self.max_sol, "max_sol");
+        cap(ctx.wallet_balance_sol, "wallet_balance");
+        if let Some(budget) = ctx.remaining_loss_budget_sol {
+            let limit = if ctx.stop_loss_pct > 0.0 {
+                budget / ctx.stop_loss_pct
+            } else {
+                budget
+            };
+            cap(limit, "loss_budget");
+        }
+
+        debug!(
+            sizer = self.sizer.name(),
+            raw_sol = raw,
+            size_sol = size,
+            limited_by = %limited_by,
+            "Position sized"
+        );
+        if size.is_nan() || size < self.min_sol {
+            return Err(StrategyError::PositionTooSmall {
+                size_sol: size,
+                min_sol: self.min_sol,
+                limited_by,
+            });
+        }
+        Ok(size)
+    }
+
+    /// `size_sol` further capped so the buy stays within the impact
+    /// limit on `curve`, failing if the round trip breaks its limit.
+    /// The minimum size is checked again after the impact cap.
+    pub fn size_order(
+        &self,
+        ctx: &SizingContext,
+        curve: &BondingCurve,
+        limit: &ImpactLimit,
+    ) -> Result<ImpactSizedOrder, StrategyError> {
+        let order = limit.size_order(curve, self.size_sol(ctx)?)?;
+        if order.size_sol < self.min_sol {
+            return Err(StrategyError::PositionTooSmall {
+                size_sol: order.size_sol,
+                min_sol: self.min_sol,
+                limited_by: "price_impact".to_string(),
+            });
+        }
+        Ok(order)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+
+    fn ctx() -> SizingContext {
+        SizingContext::new(10.0, 0.8, 0.2)
+    }
+
+    fn trade(pnl_sol: f64) -> CompletedTrade {
+        CompletedTrade {
+            position_id: "p".to_string(),
+            mint_address: "m".to_string(),
+            entry_price_usd: 1.0,
+            exit_price_usd: 1.0,
+            size_sol: 1.0,
+            pnl_sol,
+            opened_at: Utc::now(),
+            closed_at: Utc::now(),
+            exit_reason: "test".to_string(),
+            strategy_id: None,
+        }
+    }
+
+    #[test]
+    fn test_fixed_and_fraction_sizers() {
+        let fixed = PositionSizing::new(FixedSolSizer { size_sol: 0.5 }, 0.01, 2.0);
+        assert_eq!(fixed.size_sol(&ctx()).unwrap(), 0.5);
+        let fraction = PositionSizing::new(FixedFractionSizer { fraction: 0.05 }, 0.01, 2.0);
+        assert!((fraction.size_sol(&ctx()).unwrap() - 0.5).abs() < 1e-10);
+    }
+
+    #[test]
+    fn test_kelly_from_history() {
+        // 3 wins of 2.0, 2 losses of 1.0: p = 0.6, b = 2 → f* = 0.6 - 0.4 / 2 = 0.4
+        let trades: Vec<CompletedTrade> = [2.0, 2.0, 2.0, -1.0, -1.0].map(trade).to_vec();
+        let stats = KellyStats::from_trades(&trades).unwrap();
+        assert!((stats.kelly_fraction() - 0.4).abs() < 1e-10);
+        let sizing = PositionSizing::new(
+            FractionalKellySizer {
+                stats,
+                kelly_multiplier: 0.25,
+            },
+            0.01,
+            5.0,
+        );
+        // 10 SOL * 0.4 * 0.25
+        assert!((sizing.size_sol(&ctx()).unwrap() - 1.0).abs() < 1e-10);
+        assert_eq!(KellyStats::from_trades(&[trade(1.0)]), None);
+    }
+
+    #[test]
+    fn test_confidence_scaling() {
+        let sizer = ConfidenceScaledSizer {
+            base_sol: 0.1,
+            min_confidence: 0.6,
+            max_multiplier: 3.0,
+        };
+        assert_eq!(sizer.raw_size_sol(&SizingContext::new(10.0, 0.5, 0.2)), 0.0);
+        assert!((sizer.raw_size_sol(&SizingContext::new(10.0, 0.6, 0.2)) - 0.1).abs() < 1e-10);
+        assert!((sizer.raw_size_sol(&SizingContext::new(10.0, 1.0, 0.2)) - 0.3).abs() < 1e-10);
+    }
+
+    #[test]
+    fn test_caps_and_loss_budget() {
+        let sizing = PositionSizing::new(FixedSolSizer { size_sol: 5.0 }, 0.05, 2.0);
+        assert_eq!(sizing.size_sol(&ctx()).unwrap(), 2.0);
+        // 0.1 SOL left to lose at a 20% stop allows 0.5 SOL
+        let budgeted = ctx().with_loss_budget(0.1);
+        assert!((sizing.size_sol(&budgeted).unwrap() - 0.5).abs() < 1e-10);
+
+        let exhausted = ctx().with_loss_budget(0.0);
+        assert!(matches!(
+            sizing.size_sol(&exhausted),
+            Err(StrategyError::PositionTooSmall { limited_by, .. }) if limited_by == "loss_budget"
+        ));
+    }
+
+    #[test]
+    fn test_size_order_applies_impact_cap() {
+        let sizing = PositionSizing::new(FixedSolSizer { size_sol: 2.0 }, 0.05, 2.0);
+        let curve = BondingCurve::new(30.0, 1_073_000_000.0);
+        let order = sizing
+            .size_order(&ctx(), &curve, &ImpactLimit::new(100))
+            .unwrap();
+        assert!(order.capped);
+        assert!(order.size_sol < 0.31);
+        assert!(order.entry.tokens > 0.0);
+
+        let too_tight = sizing.size_order(&ctx(), &curve, &ImpactLimit::new(1));
+        assert!(matches!(
+            too_tight,
+            Err(StrategyError::PositionTooSmall { limited_by, .. }) if limited_by == "price_impact"
+        ));
+    }
+}
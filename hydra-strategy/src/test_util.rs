@@ -0,0 +1,10 @@
+use chrono::{DateTime, Utc};
+
+/// Fixed test clock, `secs` after an arbitrary epoch
+pub(crate) fn at(secs: i64) -> DateTime<Utc> {
+    at_millis(secs * 1_000)
+}
+
+pub(crate) fn at_millis(millis: i64) -> DateTime<Utc> {
+    DateTime::from_timestamp_millis(1_700_000_000_000 + millis).unwrap()
+}
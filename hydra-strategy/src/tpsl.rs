@@ -1,9 +1,20 @@
-#[derive(Debug, Clone)]
+use chrono::{DateTime, Utc};
+use hydra_core::signal::PriceTick;
+use std::time::Duration;
+use tracing::debug;
+
+use crate::error::StrategyError;
+
+/// Second ladder rung sits this many times further out than the first
+const SECOND_TRANCHE_MULTIPLE: f64 = 2.5;
+
+#[derive(Debug, Clone, PartialEq)]
 pub struct TpSlLevels {
     pub take_profit_pct: f64,
     pub stop_loss_pct: f64,
 }
 
+#[derive(Debug, Clone)]
 pub struct TpSlCalculator {
     default_tp_pct: f64,
     default_sl_pct: f64,
@@ -30,12 +41,363 @@ impl TpSlCalculator {
     }
 }
 
+impl TpSlCalculator {
+    /// `ExitPlan::scaled_out` of the levels from `calculate`
+    pub fn exit_plan(&self, ai_confidence: f64) -> ExitPlan {
+        ExitPlan::scaled_out(&self.calculate(ai_confidence))
+    }
+}
+
 impl Default for TpSlCalculator {
     fn default() -> Self {
         Self::new(0.5, 0.1)
     }
 }
 
+/// Sell `fraction` of the original position once the gain reaches `gain_pct`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TpTranche {
+    pub gain_pct: f64,
+    pub fraction: f64,
+}
+
+/// Trails the peak price by `trail_pct` once the gain reaches `activation_pct`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TrailingStop {
+    pub activation_pct: f64,
+    pub trail_pct: f64,
+}
+
+/// Require a gain of at least `min_gain_pct` once `after` has elapsed
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MinProgress {
+    pub after: Duration,
+    pub min_gain_pct: f64,
+}
+
+/// Exits that fire on time rather than price, so positions that go
+/// nowhere do not hold a slot forever
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct TimeExits {
+    pub max_hold: Option<Duration>,
+    /// Exit when the price has not made a new high for this long
+    pub no_new_high: Option<Duration>,
+    pub min_progress: Option<MinProgress>,
+}
+
+/// How a position is unwound. All percentages are fractions of the entry
+/// price (0.4 = +40%), tranche fractions are of the original position size.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExitPlan {
+    pub stop_loss_pct: f64,
+    pub tranches: Vec<TpTranche>,
+    pub trailing: Option<TrailingStop>,
+    pub breakeven_after_first_tp: bool,
+    pub time_exits: TimeExits,
+}
+
+impl ExitPlan {
+    pub fn new(stop_loss_pct: f64) -> Self {
+        Self {
+            stop_loss_pct,
+            tranches: Vec::new(),
+            trailing: None,
+            breakeven_after_first_tp: false,
+            time_exits: TimeExits::default(),
+        }
+    }
+
+    /// Half the position at the take-profit, a quarter at 2.5x that gain,
+    /// the rest on a trailing stop that activates at the first take-profit
+    /// and trails by the stop-loss distance. The stop moves to breakeven
+    /// after the first TP.
+    pub fn scaled_out(levels: &TpSlLevels) -> Self {
+        Self::new(levels.stop_loss_pct)
+            .with_tranche(levels.take_profit_pct, 0.5)
+            .with_tranche(levels.take_profit_pct * SECOND_TRANCHE_MULTIPLE, 0.25)
+            .with_trailing_stop(levels.take_profit_pct, levels.stop_loss_pct)
+            .with_breakeven_after_first_tp()
+    }
+
+    pub fn with_tranche(mut self, gain_pct: f64, fraction: f64) -> Self {
+        self.tranches.push(TpTranche { gain_pct, fraction });
+        self.tranches
+            .sort_by(|a, b| a.gain_pct.total_cmp(&b.gain_pct));
+        self
+    }
+
+    pub fn with_trailing_stop(mut self, activation_pct: f64, trail_pct: f64) -> Self {
+        self.trailing = Some(TrailingStop {
+            activation_pct,
+            trail_pct,
+        });
+        self
+    }
+
+    pub fn with_breakeven_after_first_tp(mut self) -> Self {
+        self.breakeven_after_first_tp = true;
+        self
+    }
+
+    pub fn with_max_hold(mut self, max_hold: Duration) -> Self {
+        self.time_exits.max_hold = Some(max_hold);
+        self
+    }
+
+    pub fn with_no_new_high(mut self, window: Duration) -> Self {
+        self.time_exits.no_new_high = Some(window);
+        self
+    }
+
+    pub fn with_min_progress(mut self, after: Duration, min_gain_pct: f64) -> Self {
+        self.time_exits.min_progress = Some(MinProgress {
+            after,
+            min_gain_pct,
+        });
+        self
+    }
+
+    pub fn validate(&self) -> Result<(), StrategyError> {
+        let invalid = |reason: String| Err(StrategyError::InvalidExitPlan { reason });
+        if !(0.0..1.0).contains(&self.stop_loss_pct) {
+            return invalid(format!("stop loss {} outside [0, 1)", self.stop_loss_pct));
+        }
+        if let Some(t) = self
+            .tranches
+            .iter()
+            .find(|t| t.gain_pct <= 0.0 || t.fraction <= 0.0)
+        {
+            return invalid(format!(
+                "tranche at {} selling {} must be positive",
+                t.gain_pct, t.fraction
+            ));
+        }
+        let total: f64 = self.tranches.iter().map(|t| t.fraction).sum();
+        if total > 1.0 + 1e-9 {
+            return invalid(format!("tranches sell {total} of the position"));
+        }
+        if let Some(trailing) = &self.trailing {
+            if !(0.0..1.0).contains(&trailing.trail_pct) || trailing.trail_pct == 0.0 {
+                return invalid(format!("trail {} outside (0, 1)", trailing.trail_pct));
+            }
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExitReason {
+    StopLoss,
+    /// Stop hit after it was moved up to the entry price
+    Breakeven,
+    /// Take-profit tranche, numbered from 1
+    TakeProfit(usize),
+    TrailingStop,
+    /// Held for the plan's maximum duration
+    TimeStop,
+    NoNewHigh,
+    /// Not up enough by the plan's deadline
+    MinProgress,
+}
+
+impl ExitReason {
+    /// Value stored in `CompletedTrade::exit_reason`
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::StopLoss => "stop_loss",
+            Self::Breakeven => "breakeven_stop",
+            Self::TakeProfit(_) => "take_profit",
+            Self::TrailingStop => "trailing_stop",
+            Self::TimeStop => "time_stop",
+            Self::NoNewHigh => "no_new_high",
+            Self::MinProgress => "min_progress",
+        }
+    }
+}
+
+/// Sell `fraction` of the original position
+#[derive(Debug, Clone, PartialEq)]
+pub struct SellInstruction {
+    pub fraction: f64,
+    pub price_usd: f64,
+    pub reason: ExitReason,
+}
+
+/// Tracks one open position against its `ExitPlan` tick by tick
+#[derive(Debug, Clone)]
+pub struct ExitEvaluator {
+    plan: ExitPlan,
+    entry_price_usd: f64,
+    stop_price_usd: f64,
+    peak_price_usd: f64,
+    last_price_usd: f64,
+    remaining: f64,
+    next_tranche: usize,
+    breakeven_armed: bool,
+    opened_at: DateTime<Utc>,
+    last_high_at: DateTime<Utc>,
+}
+
+impl ExitEvaluator {
+    pub fn new(plan: ExitPlan, entry_price_usd: f64) -> Self {
+        Self {
+            stop_price_usd: entry_price_usd * (1.0 - plan.stop_loss_pct),
+            peak_price_usd: entry_price_usd,
+            last_price_usd: entry_price_usd,
+            remaining: 1.0,
+            next_tranche: 0,
+            breakeven_armed: false,
+            opened_at: Utc::now(),
+            last_high_at: Utc::now(),
+            entry_price_usd,
+            plan,
+        }
+    }
+
+    /// Use the position's real open time, e.g. `Position::opened_at`
+    pub fn with_opened_at(mut self, opened_at: DateTime<Utc>) -> Self {
+        self.opened_at = opened_at;
+        self.last_high_at = opened_at;
+        self
+    }
+
+    /// Fraction of the original position still held
+    pub fn remaining(&self) -> f64 {
+        self.remaining
+    }
+
+    pub fn is_closed(&self) -> bool {
+        self.remaining <= f64::EPSILON
+    }
+
+    pub fn stop_price_usd(&self) -> f64 {
+        self.stop_price_usd
+    }
+
+    pub fn peak_price_usd(&self) -> f64 {
+        self.peak_price_usd
+    }
+
+    pub fn plan(&self) -> &ExitPlan {
+        &self.plan
+    }
+
+    /// Feed a price tick. Returns what to sell, if anything. Several
+    /// tranches crossed by one tick are merged into a single instruction.
+    /// Price exits take precedence over time exits on the same tick.
+    pub fn on_tick(&mut self, tick: &PriceTick) -> Option<SellInstruction> {
+        if self.is_closed() || self.entry_price_usd <= 0.0 {
+            return None;
+        }
+        // A bad quote must not move the peak or pass for a take-profit
+        if !tick.price_usd.is_finite() || tick.price_usd <= 0.0 {
+            return None;
+        }
+        self.last_price_usd = tick.price_usd;
+        if tick.price_usd > self.peak_price_usd {
+            self.peak_price_usd = tick.price_usd;
+            self.last_high_at = tick.timestamp;
+        }
+        self.price_exit(tick)
+            .or_else(|| self.time_exit(tick.timestamp))
+    }
+
+    /// Check time exits without a new tick, at the last seen price.
+    /// Call periodically so positions on mints that stopped trading close.
+    pub fn on_clock(&mut self, now: DateTime<Utc>) -> Option<SellInstruction> {
+        if self.is_closed() || self.entry_price_usd <= 0.0 {
+            return None;
+        }
+        self.time_exit(now)
+    }
+
+    fn time_exit(&mut self, now: DateTime<Utc>) -> Option<SellInstruction> {
+        let exits = self.plan.time_exits;
+        let held = (now - self.opened_at).to_std().unwrap_or_default();
+        let price = self.last_price_usd;
+
+        if exits.max_hold.is_some_and(|max| held >= max) {
+            return Some(self.sell_all(price, ExitReason::TimeStop));
+        }
+        if let Some(window) = exits.no_new_high {
+            let since_high = (now - self.last_high_at).to_std().unwrap_or_default();
+            if since_high >= window {
+                return Some(self.sell_all(price, ExitReason::NoNewHigh));
+            }
+        }
+        if let Some(progress) = exits.min_progress {
+            let gain = price / self.entry_price_usd - 1.0;
+            if held >= progress.after && gain < progress.min_gain_pct {
+                return Some(self.sell_all(price, ExitReason::MinProgress));
+            }
+        }
+        None
+    }
+
+    fn price_exit(&mut self, tick: &PriceTick) -> Option<SellInstruction> {
+        let price = tick.price_usd;
+
+        if price <= self.stop_price_usd {
+            let reason = if self.breakeven_armed {
+                ExitReason::Breakeven
+            } else {
+                ExitReason::StopLoss
+            };
+            return Some(self.sell_all(price, reason));
+        }
+
+        if let Some(trailing) = self.plan.trailing {
+            let peak_gain = self.peak_price_usd / self.entry_price_usd - 1.0;
+            let trail_price = self.peak_price_usd * (1.0 - trailing.trail_pct);
+            if peak_gain >= trailing.activation_pct && price <= trail_price {
+                return Some(self.sell_all(price, ExitReason::TrailingStop));
+            }
+        }
+
+        let gain = price / self.entry_price_usd - 1.0;
+        let mut fraction = 0.0;
+        let mut reason = None;
+        while let Some(tranche) = self.plan.tranches.get(self.next_tranche) {
+            if gain < tranche.gain_pct {
+                break;
+            }
+            self.next_tranche += 1;
+            fraction += tranche.fraction;
+            reason = Some(ExitReason::TakeProfit(self.next_tranche));
+        }
+        let reason = reason?;
+
+        if self.plan.breakeven_after_first_tp && !self.breakeven_armed {
+            self.breakeven_armed = true;
+            self.stop_price_usd = self.stop_price_usd.max(self.entry_price_usd);
+        }
+        let fraction = fraction.min(self.remaining);
+        self.remaining -= fraction;
+        debug!(
+            mint = %tick.mint_address,
+            fraction,
+            remaining = self.remaining,
+            gain_pct = gain,
+            "Take-profit tranche hit"
+        );
+        Some(SellInstruction {
+            fraction,
+            price_usd: price,
+            reason,
+        })
+    }
+
+    fn sell_all(&mut self, price_usd: f64, reason: ExitReason) -> SellInstruction {
+        let fraction = self.remaining;
+        self.remaining = 0.0;
+        SellInstruction {
+            fraction,
+            price_usd,
+            reason,
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -75,4 +437,147 @@ mod tests {
         // sl = 0.1 * 0.75 = 0.075
         assert!((levels.stop_loss_pct - 0.075).abs() < 1e-10);
     }
+
+    fn tick(price: f64) -> PriceTick {
+        PriceTick::new("m".to_string(), price)
+    }
+
+    fn tick_at(price: f64, at: DateTime<Utc>) -> PriceTick {
+        PriceTick {
+            timestamp: at,
+            ..tick(price)
+        }
+    }
+
+    fn ladder() -> ExitPlan {
+        ExitPlan::new(0.2)
+            .with_tranche(1.0, 0.25)
+            .with_tranche(0.4, 0.5)
+            .with_trailing_stop(0.4, 0.2)
+            .with_breakeven_after_first_tp()
+    }
+
+    #[test]
+    fn test_exit_plan_from_calculator() {
+        let plan = TpSlCalculator::new(0.4, 0.2).exit_plan(0.0);
+        assert!(plan.validate().is_ok());
+        assert_eq!(
+            plan.tranches[0],
+            TpTranche {
+                gain_pct: 0.4,
+                fraction: 0.5
+            }
+        );
+        assert!((plan.tranches[1].gain_pct - 1.0).abs() < 1e-10);
+    }
+
+    #[test]
+    fn test_invalid_plan() {
+        let plan = ExitPlan::new(0.2)
+            .with_tranche(0.4, 0.8)
+            .with_tranche(1.0, 0.5);
+        assert!(matches!(
+            plan.validate(),
+            Err(StrategyError::InvalidExitPlan { .. })
+        ));
+    }
+
+    #[test]
+    fn test_stop_loss_sells_everything() {
+        let mut eval = ExitEvaluator::new(ladder(), 1.0);
+        assert_eq!(eval.on_tick(&tick(0.9)), None);
+        let sell = eval.on_tick(&tick(0.79)).unwrap();
+        assert_eq!(sell.reason, ExitReason::StopLoss);
+        assert_eq!(sell.fraction, 1.0);
+        assert!(eval.is_closed());
+        assert_eq!(eval.on_tick(&tick(0.5)), None);
+    }
+
+    #[test]
+    fn test_bad_price_ticks_are_ignored() {
+        let mut eval = ExitEvaluator::new(ladder(), 1.0);
+        for price in [f64::NAN, f64::INFINITY, 0.0, -1.0] {
+            assert_eq!(eval.on_tick(&tick(price)), None);
+        }
+        assert_eq!(eval.remaining(), 1.0);
+        let first = eval.on_tick(&tick(1.45)).unwrap();
+        assert_eq!(first.reason, ExitReason::TakeProfit(1));
+    }
+
+    #[test]
+    fn test_ladder_breakeven_and_trailing() {
+        let mut eval = ExitEvaluator::new(ladder(), 1.0);
+        let first = eval.on_tick(&tick(1.45)).unwrap();
+        assert_eq!(first.reason, ExitReason::TakeProfit(1));
+        assert!((first.fraction - 0.5).abs() < 1e-10);
+        assert_eq!(eval.stop_price_usd(), 1.0);
+
+        let second = eval.on_tick(&tick(2.1)).unwrap();
+        assert_eq!(second.reason, ExitReason::TakeProfit(2));
+        assert!((eval.remaining() - 0.25).abs() < 1e-10);
+
+        assert_eq!(eval.on_tick(&tick(3.0)), None);
+        let trail = eval.on_tick(&tick(2.39)).unwrap();
+        assert_eq!(trail.reason, ExitReason::TrailingStop);
+        assert!((trail.fraction - 0.25).abs() < 1e-10);
+        assert!(eval.is_closed());
+    }
+
+    #[test]
+    fn test_gap_merges_tranches_and_breakeven_stop() {
+        let plan = ExitPlan::new(0.2)
+            .with_tranche(0.4, 0.5)
+            .with_tranche(1.0, 0.25)
+            .with_breakeven_after_first_tp();
+        let mut eval = ExitEvaluator::new(plan, 1.0);
+        let sell = eval.on_tick(&tick(2.5)).unwrap();
+        assert_eq!(sell.reason, ExitReason::TakeProfit(2));
+        assert!((sell.fraction - 0.75).abs() < 1e-10);
+        let stop = eval.on_tick(&tick(0.99)).unwrap();
+        assert_eq!(stop.reason, ExitReason::Breakeven);
+        assert_eq!(stop.reason.as_str(), "breakeven_stop");
+    }
+
+    #[test]
+    fn test_max_hold_time_stop() {
+        let opened = Utc::now();
+        let plan = ExitPlan::new(0.2).with_max_hold(Duration::from_secs(60));
+        let mut eval = ExitEvaluator::new(plan, 1.0).with_opened_at(opened);
+        assert_eq!(
+            eval.on_tick(&tick_at(1.05, opened + chrono::Duration::seconds(30))),
+            None
+        );
+        let sell = eval
+            .on_clock(opened + chrono::Duration::seconds(61))
+            .unwrap();
+        assert_eq!(sell.reason.as_str(), "time_stop");
+        assert_eq!(sell.price_usd, 1.05);
+        assert_eq!(sell.fraction, 1.0);
+    }
+
+    #[test]
+    fn test_no_new_high() {
+        let opened = Utc::now();
+        let at = |secs| opened + chrono::Duration::seconds(secs);
+        let plan = ExitPlan::new(0.5).with_no_new_high(Duration::from_secs(20));
+        let mut eval = ExitEvaluator::new(plan, 1.0).with_opened_at(opened);
+        assert_eq!(eval.on_tick(&tick_at(1.1, at(15))), None);
+        assert_eq!(eval.on_tick(&tick_at(1.05, at(30))), None);
+        let sell = eval.on_tick(&tick_at(1.08, at(36))).unwrap();
+        assert_eq!(sell.reason, ExitReason::NoNewHigh);
+    }
+
+    #[test]
+    fn test_min_progress() {
+        let opened = Utc::now();
+        let at = |secs| opened + chrono::Duration::seconds(secs);
+        let plan = ExitPlan::new(0.5).with_min_progress(Duration::from_secs(30), 0.1);
+        let mut on_track = ExitEvaluator::new(plan.clone(), 1.0).with_opened_at(opened);
+        assert_eq!(on_track.on_tick(&tick_at(1.15, at(31))), None);
+
+        let mut stalled = ExitEvaluator::new(plan, 1.0).with_opened_at(opened);
+        assert_eq!(stalled.on_tick(&tick_at(1.02, at(10))), None);
+        let sell = stalled.on_tick(&tick_at(1.03, at(31))).unwrap();
+        assert_eq!(sell.reason, ExitReason::MinProgress);
+    }
 }
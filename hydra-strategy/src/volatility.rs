@@ -0,0 +1,239 @@
+use hydra_core::signal::PriceTick;
+use tracing::debug;
+
+use crate::tpsl::{ExitPlan, TpSlCalculator, TpSlLevels};
+
+/// How volatility is measured from a series of ticks
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum VolatilityMeasure {
+    /// Standard deviation of tick-to-tick log returns
+    RealizedStdDev,
+    /// Mean true range of bars of `bar_ticks` ticks, relative to the
+    /// previous close, like ATR on fixed-size bars
+    AverageTrueRange { bar_ticks: usize },
+}
+
+/// Realized volatility of `ticks`, which must be in time order.
+/// `None` when there are too few usable prices.
+pub fn measure_volatility(ticks: &[PriceTick], measure: VolatilityMeasure) -> Option<f64> {
+    let prices: Vec<f64> = ticks
+        .iter()
+        .map(|t| t.price_usd)
+        .filter(|p| *p > 0.0 && p.is_finite())
+        .collect();
+    match measure {
+        VolatilityMeasure::RealizedStdDev => {
+            let returns: Vec<f64> = prices.windows(2).map(|w| (w[1] / w[0]).ln()).collect();
+            if returns.len() < 2 {
+                return None;
+            }
+            let n = returns.len() as f64;
+            let mean = returns.iter().sum::<f64>() / n;
+            let variance = returns.iter().map(|r| (r - mean).powi(2)).sum::<f64>() / (n - 1.0);
+            Some(variance.sqrt())
+        }
+        VolatilityMeasure::AverageTrueRange { bar_ticks } => {
+            let bars: Vec<(f64, f64, f64)> = prices
+                .chunks(bar_ticks.max(1))
+                .map(|bar| {
+                    let high = bar.iter().copied().fold(f64::MIN, f64::max);
+                    let low = bar.iter().copied().fold(f64::MAX, f64::min);
+                    (high, low, bar[bar.len() - 1])
+                })
+                .collect();
+            if bars.len() < 2 {
+                return None;
+            }
+            let ranges: Vec<f64> = bars
+                .windows(2)
+                .map(|w| {
+                    let prev_close = w[0].2;
+                    let (high, low, _) = w[1];
+                    (high.max(prev_close) - low.min(prev_close)) / prev_close
+                })
+                .collect();
+            Some(ranges.iter().sum::<f64>() / ranges.len() as f64)
+        }
+    }
+}
+
+/// Sets TP and SL as multiples of the mint's recent volatility instead of
+/// fixed percentages. Falls back to a plain `TpSlCalculator` until enough
+/// ticks have been seen.
+#[derive(Debug, Clone)]
+pub struct VolatilityTpSlCalculator {
+    pub measure: VolatilityMeasure,
+    pub min_ticks: usize,
+    pub sl_multiple: f64,
+    pub tp_multiple: f64,
+    pub sl_floor_pct: f64,
+    pub sl_cap_pct: f64,
+    pub tp_floor_pct: f64,
+    pub tp_cap_pct: f64,
+    /// How strongly AI confidence widens TP and tightens SL, 0 disables it
+    pub confidence_tilt: f64,
+    fallback: TpSlCalculator,
+}
+
+impl VolatilityTpSlCalculator {
+    pub fn new(measure: VolatilityMeasure, sl_multiple: f64, tp_multiple: f64) -> Self {
+        Self {
+            measure,
+            min_ticks: 20,
+            sl_multiple,
+            tp_multiple,
+            sl_floor_pct: 0.05,
+            sl_cap_pct: 0.35,
+            tp_floor_pct: 0.10,
+            tp_cap_pct: 3.0,
+            confidence_tilt: 0.5,
+            fallback: TpSlCalculator::default(),
+        }
+    }
+
+    pub fn with_sl_bounds(mut self, floor_pct: f64, cap_pct: f64) -> Self {
+        self.sl_floor_pct = floor_pct;
+        self.sl_cap_pct = cap_pct;
+        self
+    }
+
+    pub fn with_tp_bounds(mut self, floor_pct: f64, cap_pct: f64) -> Self {
+        self.tp_floor_pct = floor_pct;
+        self.tp_cap_pct = cap_pct;
+        self
+    }
+
+    pub fn with_confidence_tilt(mut self, tilt: f64) -> Self {
+        self.confidence_tilt = tilt;
+        self
+    }
+
+    pub fn with_min_ticks(mut self, min_ticks: usize) -> Self {
+        self.min_ticks = min_ticks;
+        self
+    }
+
+    pub fn with_fallback(mut self, fallback: TpSlCalculator) -> Self {
+        self.fallback = fallback;
+        self
+    }
+
+    /// Levels for a mint given its recent ticks in time order.
+    /// Higher confidence -> wider take profit, tighter stop loss.
+    pub fn calculate(&self, ticks: &[PriceTick], ai_confidence: f64) -> TpSlLevels {
+        let volatility = if ticks.len() >= self.min_ticks {
+            measure_volatility(ticks, self.measure)
+        } else {
+            None
+        };
+        let Some(volatility) = volatility else {
+            return self.fallback.calculate(ai_confidence);
+        };
+
+        let confidence = ai_confidence.clamp(0.0, 1.0) * self.confidence_tilt;
+        let tp = volatility * self.tp_multiple * (1.0 + confidence);
+        let sl = volatility * self.sl_multiple * (1.0 - confidence * 0.5);
+        // Not `clamp`: bounds are public and may be inverted or NaN, in
+        // which case the cap wins and a NaN bound is ignored
+        let levels = TpSlLevels {
+            take_profit_pct: tp.max(self.tp_floor_pct).min(self.tp_cap_pct),
+            stop_loss_pct: sl.max(self.sl_floor_pct).min(self.sl_cap_pct),
+        };
+        debug!(
+            volatility,
+            take_profit_pct = levels.take_profit_pct,
+            stop_loss_pct = levels.stop_loss_pct,
+            "Volatility-scaled TP/SL"
+        );
+        levels
+    }
+
+    pub fn exit_plan(&self, ticks: &[PriceTick], ai_confidence: f64) -> ExitPlan {
+        ExitPlan::scaled_out(&self.calculate(ticks, ai_confidence))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ticks(prices: &[f64]) -> Vec<PriceTick> {
+        prices
+            .iter()
+            .map(|p| PriceTick::new("m".to_string(), *p))
+            .collect()
+    }
+
+    /// Prices alternating up and down by `swing`
+    fn zigzag(swing: f64, n: usize) -> Vec<PriceTick> {
+        let prices: Vec<f64> = (0..n)
+            .map(|i| if i % 2 == 0 { 1.0 } else { 1.0 + swing })
+            .collect();
+        ticks(&prices)
+    }
+
+    #[test]
+    fn test_realized_volatility() {
+        assert_eq!(
+            measure_volatility(&ticks(&[1.0, 1.1]), VolatilityMeasure::RealizedStdDev),
+            None
+        );
+        let flat = measure_volatility(&ticks(&[1.0; 10]), VolatilityMeasure::RealizedStdDev);
+        assert_eq!(flat, Some(0.0));
+        let calm =
+            measure_volatility(&zigzag(0.01, 30), VolatilityMeasure::RealizedStdDev).unwrap();
+        let wild = measure_volatility(&zigzag(0.2, 30), VolatilityMeasure::RealizedStdDev).unwrap();
+        assert!(wild > calm * 10.0);
+    }
+
+    #[test]
+    fn test_average_true_range() {
+        // bars [1.0, 1.2] and [1.1, 0.9]: true range = (1.2 - 0.9) / 1.2
+        let atr = measure_volatility(
+            &ticks(&[1.0, 1.2, 1.1, 0.9]),
+            VolatilityMeasure::AverageTrueRange { bar_ticks: 2 },
+        )
+        .unwrap();
+        assert!((atr - 0.25).abs() < 1e-10);
+    }
+
+    #[test]
+    fn test_levels_scale_with_volatility_and_respect_bounds() {
+        let calc = VolatilityTpSlCalculator::new(VolatilityMeasure::RealizedStdDev, 2.0, 4.0)
+            .with_confidence_tilt(0.0)
+            .with_min_ticks(10);
+        let calm = calc.calculate(&zigzag(0.01, 30), 0.5);
+        assert_eq!(calm.stop_loss_pct, calc.sl_floor_pct);
+        let mid = calc.calculate(&zigzag(0.05, 30), 0.5);
+        let vol = measure_volatility(&zigzag(0.05, 30), VolatilityMeasure::RealizedStdDev).unwrap();
+        assert!((mid.stop_loss_pct - vol * 2.0).abs() < 1e-10);
+        assert!((mid.take_profit_pct - vol * 4.0).abs() < 1e-10);
+        let wild = calc.calculate(&zigzag(1.0, 30), 0.5);
+        assert_eq!(wild.stop_loss_pct, calc.sl_cap_pct);
+    }
+
+    #[test]
+    fn test_confidence_tilt_and_fallback() {
+        let calc = VolatilityTpSlCalculator::new(VolatilityMeasure::RealizedStdDev, 2.0, 4.0)
+            .with_min_ticks(10);
+        let low = calc.calculate(&zigzag(0.05, 30), 0.0);
+        let high = calc.calculate(&zigzag(0.05, 30), 1.0);
+        assert!(high.take_profit_pct > low.take_profit_pct);
+        assert!(high.stop_loss_pct < low.stop_loss_pct);
+
+        let few = calc.calculate(&zigzag(0.05, 5), 0.5);
+        let fallback = TpSlCalculator::default().calculate(0.5);
+        assert_eq!(few.stop_loss_pct, fallback.stop_loss_pct);
+    }
+
+    #[test]
+    fn test_inverted_or_nan_bounds_do_not_panic() {
+        let calc = VolatilityTpSlCalculator::new(VolatilityMeasure::RealizedStdDev, 2.0, 4.0)
+            .with_min_ticks(10)
+            .with_tp_bounds(0.5, 0.2)
+            .with_sl_bounds(f64::NAN, 0.3);
+        let levels = calc.calculate(&zigzag(0.01, 30), 0.5);
+        assert_eq!(levels.take_profit_pct, 0.2);
+        assert!(levels.stop_loss_pct > 0.0 && levels.stop_loss_pct <= 0.3);
+    }
+}
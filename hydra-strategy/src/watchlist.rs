@@ -0,0 +1,499 @@
+use chrono::{DateTime, Utc};
+use hydra_core::signal::{MintSignal, PriceTick, TradeEvent};
+use std::collections::VecDeque;
+use std::sync::Mutex;
+use std::time::Duration;
+use tracing::{debug, info};
+
+use crate::chain::FilterChain;
+use crate::filters::Decision;
+use crate::mint_table::MintTable;
+
+/// Decided candidates kept for tuning before the oldest are dropped
+const MAX_RECORDS: usize = 10_000;
+/// How long a due candidate may go without `confirm` or `release`
+const DEFAULT_PENDING_TIMEOUT_SECS: u64 = 60;
+
+/// How long a candidate is watched before it is re-evaluated
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ConfirmWindow {
+    Time(Duration),
+    /// Until `count` trades have been seen, or expire after `timeout`
+    Trades {
+        count: usize,
+        timeout: Duration,
+    },
+}
+
+/// What must still hold when the window closes
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConfirmCriteria {
+    pub min_buys_per_sec: f64,
+    pub min_buy_sell_ratio: f64,
+    pub min_holder_growth_per_min: f64,
+    /// Largest price drop since the candidate was first seen
+    pub max_price_drop_pct: f64,
+    /// Require an AI re-score of at least this much
+    pub min_rescore: Option<f64>,
+}
+
+impl Default for ConfirmCriteria {
+    fn default() -> Self {
+        Self {
+            min_buys_per_sec: 0.0,
+            min_buy_sell_ratio: 1.0,
+            min_holder_growth_per_min: 0.0,
+            max_price_drop_pct: 0.2,
+            min_rescore: None,
+        }
+    }
+}
+
+/// A candidate whose window has closed, waiting for `Watchlist::confirm`
+#[derive(Debug, Clone)]
+pub struct Candidate {
+    pub signal: MintSignal,
+    pub watched_at: DateTime<Utc>,
+    pub trades: usize,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum WatchOutcome {
+    Entered,
+    Rejected {
+        reason: String,
+    },
+    /// Not enough trades before the timeout, or never confirmed once due
+    Expired,
+}
+
+impl WatchOutcome {
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::Entered => "entered",
+            Self::Rejected { reason } => reason,
+            Self::Expired => "expired",
+        }
+    }
+}
+
+/// One decided candidate, followed afterwards to see what the delay cost
+#[derive(Debug, Clone, PartialEq)]
+pub struct WatchRecord {
+    pub mint_address: String,
+    pub watched_at: DateTime<Utc>,
+    pub decided_at: DateTime<Utc>,
+    pub watch_price_usd: f64,
+    pub decision_price_usd: f64,
+    /// Highest price seen after the decision
+    pub peak_after_usd: f64,
+    pub outcome: WatchOutcome,
+}
+
+impl WatchRecord {
+    /// Best gain available after the decision, relative to its price
+    pub fn move_after_pct(&self) -> f64 {
+        if self.decision_price_usd <= 0.0 {
+            return 0.0;
+        }
+        self.peak_after_usd / self.decision_price_usd - 1.0
+    }
+
+    /// Price change paid for waiting, relative to the first sighting
+    pub fn delay_cost_pct(&self) -> f64 {
+        if self.watch_price_usd <= 0.0 {
+            return 0.0;
+        }
+        self.decision_price_usd / self.watch_price_usd - 1.0
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct WatchStats {
+    pub watching: usize,
+    pub entered: usize,
+    pub rejected: usize,
+    pub expired: usize,
+    /// Rejected or expired candidates that went on to gain the given amount
+    pub missed_moves: usize,
+}
+
+struct Watched {
+    signal: MintSignal,
+    watched_at: DateTime<Utc>,
+    trades: usize,
+    /// When `due` returned it, while not yet confirmed
+    pending_since: Option<DateTime<Utc>>,
+}
+
+/// Delayed-entry mode: candidates wait out a confirmation window and are
+/// re-checked before entering. The caller refreshes the signal (flow
+/// features, holders, optionally an AI re-score) for each due candidate.
+pub struct Watchlist {
+    window: ConfirmWindow,
+    criteria: ConfirmCriteria,
+    chain: Option<FilterChain>,
+    pending_timeout: Duration,
+    watching: Mutex<MintTable<Watched>>,
+    records: Mutex<VecDeque<WatchRecord>>,
+}
+
+impl Watchlist {
+    pub fn new(window: ConfirmWindow, criteria: ConfirmCriteria) -> Self {
+        Self {
+            window,
+            criteria,
+            chain: None,
+            pending_timeout: Duration::from_secs(DEFAULT_PENDING_TIMEOUT_SECS),
+            watching: Mutex::new(MintTable::default()),
+            records: Mutex::new(VecDeque::new()),
+        }
+    }
+
+    /// Filters run again on the refreshed signal
+    pub fn with_chain(mut self, chain: FilterChain) -> Self {
+        self.chain = Some(chain);
+        self
+    }
+
+    /// Due candidates not confirmed or released within `timeout` expire
+    pub fn with_pending_timeout(mut self, timeout: Duration) -> Self {
+        self.pending_timeout = timeout;
+        self
+    }
+
+    fn lock_watching(&self) -> std::sync::MutexGuard<'_, MintTable<Watched>> {
+        self.watching.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn lock_records(&self) -> std::sync::MutexGuard<'_, VecDeque<WatchRecord>> {
+        self.records.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Start watching a candidate. False if it is already watched.
+    pub fn watch(&self, signal: MintSignal, now: DateTime<Utc>) -> bool {
+        let mut watching = self.lock_watching();
+        if watching.contains_key(&signal.mint_address) {
+            return false;
+        }
+        debug!(mint = %signal.mint_address, "Candidate watched");
+        watching.insert(
+            signal.mint_address.clone(),
+            Watched {
+                signal,
+                watched_at: now,
+                trades: 0,
+                pending_since: None,
+            },
+        );
+        true
+    }
+
+    pub fn on_trade(&self, event: &TradeEvent) {
+        if let Some(w) = self.lock_watching().get_mut(&event.mint_address) {
+            w.trades += 1;
+        }
+    }
+
+    /// Follow prices of decided candidates for missed-move tracking
+    pub fn on_price(&self, tick: &PriceTick) {
+        for record in self
+            .lock_records()
+            .iter_mut()
+            .filter(|r| r.mint_address == tick.mint_address)
+        {
+            record.peak_after_usd = record.peak_after_usd.max(tick.price_usd);
+        }
+    }
+
+    /// Candidates whose window has closed. Each is returned once and
+    /// stays pending until `confirm` or `release`. Candidates that timed
+    /// out waiting for trades, or stayed pending past the pending
+    /// timeout, are recorded as expired and not returned.
+    pub fn due(&self, now: DateTime<Utc>) -> Vec<Candidate> {
+        let mut watching = self.lock_watching();
+        let mut due = Vec::new();
+        let mut expired = Vec::new();
+        for (mint, w) in watching.iter_mut() {
+            if let Some(since) = w.pending_since {
+                if (now - since).to_std().unwrap_or_default() >= self.pending_timeout {
+                    expired.push(mint.clone());
+                }
+                continue;
+            }
+            let waited = (now - w.watched_at).to_std().unwrap_or_default();
+            match self.window {
+                ConfirmWindow::Time(window) if waited >= window => due.push(Candidate {
+                    signal: w.signal.clone(),
+                    watched_at: w.watched_at,
+                    trades: w.trades,
+                }),
+                ConfirmWindow::Trades { count, .. } if w.trades >= count => due.push(Candidate {
+                    signal: w.signal.clone(),
+                    watched_at: w.watched_at,
+                    trades: w.trades,
+                }),
+                ConfirmWindow::Trades { timeout, .. } if waited >= timeout => {
+                    expired.push(mint.clone())
+                }
+                _ => {}
+            }
+        }
+        for candidate in &due {
+            if let Some(w) = watching.get_mut(&candidate.signal.mint_address) {
+                w.pending_since = Some(now);
+            }
+        }
+        for mint in expired {
+            if let Some(w) = watching.remove(&mint) {
+                let price = w.signal.price_usd;
+                self.record(&w, now, price, WatchOutcome::Expired);
+            }
+        }
+        due
+    }
+
+    /// Return a pending candidate to the next `due`, e.g. after its
+    /// re-score failed
+    pub fn release(&self, mint_address: &str) {
+        if let Some(w) = self.lock_watching().get_mut(mint_address) {
+            w.pending_since = None;
+        }
+    }
+
+    /// Decide a due candidate from its refreshed signal and, if the
+    /// criteria ask for one, an AI re-score
+    pub fn confirm(
+        &self,
+        refreshed: &MintSignal,
+        rescore: Option<f64>,
+        now: DateTime<Utc>,
+    ) -> Option<WatchOutcome> {
+        let watched = self.lock_watching().remove(&refreshed.mint_address)?;
+        let outcome = match self.check(&watched.signal, refreshed, rescore) {
+            None => WatchOutcome::Entered,
+            Some(reason) => WatchOutcome::Rejected { reason },
+        };
+        self.record(&watched, now, refreshed.price_usd, outcome.clone());
+        Some(outcome)
+    }
+
+    fn check(
+        &self,
+        first: &MintSignal,
+        refreshed: &MintSignal,
+        rescore: Option<f64>,
+    ) -> Option<String> {
+        if let Some(chain) = &self.chain {
+            if let Some(Decision::Reject { reason, .. }) =
+                chain.evaluate(refreshed).rejections.into_iter().next()
+            {
+                return Some(reason);
+            }
+        }
+        let c = &self.criteria;
+        if let Some(min) = c.min_rescore {
+            if rescore.is_none_or(|score| score < min) {
+                return Some("rescore".to_string());
+            }
+        }
+        if refreshed.holder_count < first.holder_count {
+            return Some("holders_falling".to_string());
+        }
+        if first.price_usd > 0.0
+            && refreshed.price_usd / first.price_usd - 1.0 < -c.max_price_drop_pct
+        {
+            return Some("price_drop".to_string());
+        }
+        let Some(flow) = &refreshed.flow else {
+            return Some("no_flow".to_string());
+        };
+        if flow.buys_per_sec < c.min_buys_per_sec {
+            return Some("momentum".to_string());
+        }
+        if flow.buy_sell_volume_ratio < c.min_buy_sell_ratio {
+            return Some("sell_pressure".to_string());
+        }
+        if flow.holder_growth_per_min < c.min_holder_growth_per_min {
+            return Some("holder_growth".to_string());
+        }
+        None
+    }
+
+    fn record(&self, watched: &Watched, now: DateTime<Utc>, price: f64, outcome: WatchOutcome) {
+        info!(
+            mint = %watched.signal.mint_address,
+            outcome = outcome.as_str(),
+            trades = watched.trades,
+            waited_ms = (now - watched.watched_at).num_milliseconds(),
+            "Watch decided"
+        );
+        let mut records = self.lock_records();
+        if records.len() >= MAX_RECORDS {
+            records.pop_front();
+        }
+        records.push_back(WatchRecord {
+            mint_address: watched.signal.mint_address.clone(),
+            watched_at: watched.watched_at,
+            decided_at: now,
+            watch_price_usd: watched.signal.price_usd,
+            decision_price_usd: price,
+            peak_after_usd: price,
+            outcome,
+        });
+    }
+
+    pub fn records(&self) -> Vec<WatchRecord> {
+        self.lock_records().iter().cloned().collect()
+    }
+
+    /// Counts so far. A miss is a candidate we passed on whose price
+    /// later rose by at least `missed_gain_pct`.
+    pub fn stats(&self, missed_gain_pct: f64) -> WatchStats {
+        let mut stats = WatchStats {
+            watching: self.lock_watching().len(),
+            ..WatchStats::default()
+        };
+        for record in self.lock_records().iter() {
+            match record.outcome {
+                WatchOutcome::Entered => stats.entered += 1,
+                WatchOutcome::Rejected { .. } => stats.rejected += 1,
+                WatchOutcome::Expired => stats.expired += 1,
+            }
+            if record.outcome != WatchOutcome::Entered && record.move_after_pct() >= missed_gain_pct
+            {
+                stats.missed_moves += 1;
+            }
+        }
+        stats
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chain::ChainMode;
+    use crate::filters::McapFilter;
+    use crate::test_util::at;
+    use hydra_core::signal::{FlowFeatures, TradeKind};
+
+    fn signal(price: f64, holders: u64, buys_per_sec: f64) -> MintSignal {
+        MintSignal::new(
+            "m".to_string(),
+            10_000.0,
+            0.0,
+            price,
+            holders,
+            5_000.0,
+            10.0,
+        )
+        .with_flow(FlowFeatures {
+            buys_per_sec,
+            buy_sell_volume_ratio: 2.0,
+            ..FlowFeatures::default()
+        })
+    }
+
+    fn criteria() -> ConfirmCriteria {
+        ConfirmCriteria {
+            min_buys_per_sec: 1.0,
+            ..ConfirmCriteria::default()
+        }
+    }
+
+    #[test]
+    fn test_time_window_and_confirmation() {
+        let list = Watchlist::new(ConfirmWindow::Time(Duration::from_secs(10)), criteria());
+        assert!(list.watch(signal(1.0, 50, 0.0), at(0)));
+        assert!(!list.watch(signal(1.0, 50, 0.0), at(1)));
+        assert!(list.due(at(5)).is_empty());
+        assert_eq!(list.due(at(10)).len(), 1);
+
+        let outcome = list.confirm(&signal(1.1, 80, 2.0), None, at(10));
+        assert_eq!(outcome, Some(WatchOutcome::Entered));
+        assert!(list.confirm(&signal(1.1, 80, 2.0), None, at(11)).is_none());
+        let record = &list.records()[0];
+        assert!((record.delay_cost_pct() - 0.1).abs() < 1e-10);
+    }
+
+    #[test]
+    fn test_due_candidate_is_returned_once_until_released() {
+        let list = Watchlist::new(ConfirmWindow::Time(Duration::from_secs(10)), criteria());
+        list.watch(signal(1.0, 50, 0.0), at(0));
+        assert_eq!(list.due(at(10)).len(), 1);
+        assert!(list.due(at(11)).is_empty());
+
+        list.release("m");
+        assert_eq!(list.due(at(12)).len(), 1);
+        let outcome = list.confirm(&signal(1.1, 80, 2.0), None, at(12));
+        assert_eq!(outcome, Some(WatchOutcome::Entered));
+        assert!(list.due(at(13)).is_empty());
+    }
+
+    #[test]
+    fn test_unconfirmed_candidate_expires() {
+        let list = Watchlist::new(ConfirmWindow::Time(Duration::from_secs(10)), criteria())
+            .with_pending_timeout(Duration::from_secs(30));
+        list.watch(signal(1.0, 50, 0.0), at(0));
+        assert_eq!(list.due(at(10)).len(), 1);
+        assert!(list.due(at(39)).is_empty());
+        assert_eq!(list.stats(0.5).watching, 1);
+
+        assert!(list.due(at(40)).is_empty());
+        let stats = list.stats(0.5);
+        assert_eq!(stats.watching, 0);
+        assert_eq!(stats.expired, 1);
+        assert!(list.confirm(&signal(1.1, 80, 2.0), None, at(41)).is_none());
+    }
+
+    #[test]
+    fn test_rejections_and_missed_moves() {
+        let list = Watchlist::new(ConfirmWindow::Time(Duration::ZERO), criteria()).with_chain(
+            FilterChain::new(ChainMode::ShortCircuit).with_filter(McapFilter::new(0.0, 5_000.0)),
+        );
+        list.watch(signal(1.0, 50, 0.0), at(0));
+        let outcome = list.confirm(&signal(1.0, 50, 2.0), None, at(5)).unwrap();
+        assert_eq!(outcome.as_str(), "above_max");
+
+        let list = Watchlist::new(ConfirmWindow::Time(Duration::ZERO), criteria());
+        list.watch(signal(1.0, 50, 0.0), at(0));
+        let outcome = list.confirm(&signal(1.0, 60, 0.5), None, at(5)).unwrap();
+        assert_eq!(outcome.as_str(), "momentum");
+        list.on_price(&PriceTick::new("m".to_string(), 1.8));
+
+        let stats = list.stats(0.5);
+        assert_eq!(stats.rejected, 1);
+        assert_eq!(stats.missed_moves, 1);
+        assert_eq!(list.stats(1.0).missed_moves, 0);
+    }
+
+    #[test]
+    fn test_trade_window_rescore_and_expiry() {
+        let window = ConfirmWindow::Trades {
+            count: 2,
+            timeout: Duration::from_secs(30),
+        };
+        let rescored = ConfirmCriteria {
+            min_rescore: Some(0.7),
+            ..criteria()
+        };
+        let list = Watchlist::new(window, rescored);
+        list.watch(signal(1.0, 50, 0.0), at(0));
+        let trade = TradeEvent::new("m".to_string(), "b".to_string(), TradeKind::Buy, 0.1, 1.0);
+        list.on_trade(&trade);
+        assert!(list.due(at(5)).is_empty());
+        list.on_trade(&trade);
+        assert_eq!(list.due(at(6))[0].trades, 2);
+        let outcome = list
+            .confirm(&signal(1.0, 50, 2.0), Some(0.6), at(6))
+            .unwrap();
+        assert_eq!(outcome.as_str(), "rescore");
+
+        let mut quiet = signal(1.0, 50, 0.0);
+        quiet.mint_address = "quiet".to_string();
+        list.watch(quiet, at(0));
+        assert!(list.due(at(30)).is_empty());
+        assert_eq!(list.stats(0.5).expired, 1);
+        assert_eq!(list.stats(0.5).watching, 0);
+    }
+}
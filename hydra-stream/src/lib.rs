@@ -1,5 +1,7 @@
+pub mod metadata;
 pub mod parser;
 pub mod reconnect;
 
+pub use metadata::MetadataResolver;
 pub use parser::PumpfunParser;
 pub use reconnect::StreamReconnect;
@@ -0,0 +1,249 @@
+use anyhow::{Context, Result};
+use dashmap::DashMap;
+use hydra_core::config::HydraConfig;
+use hydra_core::signal::{MintSignal, TokenMetadata};
+use std::time::{Duration, Instant};
+use tracing::{debug, warn};
+
+const DEFAULT_TIMEOUT_MS: u64 = 1_500;
+const DEFAULT_CACHE_TTL_SECS: u64 = 3_600;
+const DEFAULT_CACHE_CAPACITY: usize = 10_000;
+const MAX_DESCRIPTION_CHARS: usize = 1_000;
+
+/// Gateways whose `/ipfs/<cid>` links are rewritten onto our own gateway
+const PUBLIC_IPFS_HOSTS: &[&str] = &[
+    "ipfs.io",
+    "cf-ipfs.com",
+    "gateway.pinata.cloud",
+    "cloudflare-ipfs.com",
+    "dweb.link",
+];
+
+/// Resolves a create event `uri` into its off-chain metadata JSON through
+/// a configurable IPFS/HTTP gateway. Results are cached per uri.
+pub struct MetadataResolver {
+    client: reqwest::Client,
+    gateway_url: String,
+    timeout: Duration,
+    cache_ttl: Duration,
+    cache_capacity: usize,
+    cache: DashMap<String, (TokenMetadata, Instant)>,
+}
+
+impl MetadataResolver {
+    pub fn new(gateway_url: String) -> Self {
+        Self {
+            client: reqwest::Client::new(),
+            gateway_url: gateway_url.trim_end_matches('/').to_string(),
+            timeout: Duration::from_millis(DEFAULT_TIMEOUT_MS),
+            cache_ttl: Duration::from_secs(DEFAULT_CACHE_TTL_SECS),
+            cache_capacity: DEFAULT_CACHE_CAPACITY,
+            cache: DashMap::new(),
+        }
+    }
+
+    /// Resolver for `config`'s gateway and timeout
+    pub fn from_config(config: &HydraConfig) -> Self {
+        Self::new(config.metadata_gateway_url.clone())
+            .with_timeout(Duration::from_millis(config.metadata_timeout_ms))
+    }
+
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    pub fn with_cache(mut self, ttl: Duration, capacity: usize) -> Self {
+        self.cache_ttl = ttl;
+        self.cache_capacity = capacity.max(1);
+        self
+    }
+
+    /// Map a metadata uri onto a fetchable URL. `ipfs://` and public
+    /// gateway links go through our gateway, other http(s) links are kept.
+    pub fn gateway_url_for(&self, uri: &str) -> Option<String> {
+        let uri = uri.trim();
+        if let Some(path) = uri.strip_prefix("ipfs://") {
+            let path = path.trim_start_matches("ipfs/");
+            return Some(format!("{}/ipfs/{}", self.gateway_url, path));
+        }
+        if !(uri.starts_with("https://") || uri.starts_with("http://")) {
+            return None;
+        }
+        let without_scheme = uri.split_once("://").map(|(_, rest)| rest)?;
+        let (host, path) = without_scheme
+            .split_once('/')
+            .unwrap_or((without_scheme, ""));
+        if PUBLIC_IPFS_HOSTS.contains(&host) && path.starts_with("ipfs/") {
+            return Some(format!("{}/{}", self.gateway_url, path));
+        }
+        Some(uri.to_string())
+    }
+
+    pub async fn resolve(&self, uri: &str) -> Result<TokenMetadata> {
+        if let Some(entry) = self.cache.get(uri) {
+            if entry.1.elapsed() < self.cache_ttl {
+                return Ok(entry.0.clone());
+            }
+        }
+
+        let url = self
+            .gateway_url_for(uri)
+            .ok_or_else(|| anyhow::anyhow!("Unsupported metadata uri: {uri}"))?;
+        debug!(uri, url, "Fetching token metadata");
+
+        let response = tokio::time::timeout(self.timeout, self.client.get(&url).send())
+            .await
+            .context("Metadata request timed out")?
+            .context("Metadata request failed")?
+            .error_for_status()
+            .context("Metadata gateway returned an error status")?;
+        let mut metadata: TokenMetadata = tokio::time::timeout(self.timeout, response.json())
+            .await
+            .context("Metadata body timed out")?
+            .context("Failed to deserialize token metadata")?;
+
+        if let Some(description) = &mut metadata.description {
+            if description.chars().count() > MAX_DESCRIPTION_CHARS {
+                *description = description.chars().take(MAX_DESCRIPTION_CHARS).collect();
+            }
+        }
+
+        self.insert(uri, metadata.clone());
+        Ok(metadata)
+    }
+
+    /// Attach metadata to a signal that carries a `uri`.
+    /// Returns false when there is no uri or resolution failed.
+    pub async fn enrich(&self, signal: &mut MintSignal) -> bool {
+        let Some(uri) = signal.uri.clone() else {
+            return false;
+        };
+        match self.resolve(&uri).await {
+            Ok(metadata) => {
+                signal.metadata = Some(metadata);
+                true
+            }
+            Err(e) => {
+                warn!(mint = %signal.mint_address, uri, error = %e, "Metadata enrichment failed");
+                false
+            }
+        }
+    }
+
+    pub fn cached(&self) -> usize {
+        self.cache.len()
+    }
+
+    fn insert(&self, uri: &str, metadata: TokenMetadata) {
+        if self.cache.len() >= self.cache_capacity {
+            let ttl = self.cache_ttl;
+            self.cache.retain(|_, (_, at)| at.elapsed() < ttl);
+            if self.cache.len() >= self.cache_capacity {
+                self.cache.clear();
+            }
+        }
+        self.cache
+            .insert(uri.to_string(), (metadata, Instant::now()));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    /// HTTP stand-in that serves `body` for every request and counts hits
+    async fn serve(body: &'static str, delay: Duration) -> (String, Arc<AtomicUsize>) {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let hits = Arc::new(AtomicUsize::new(0));
+        let counter = hits.clone();
+        tokio::spawn(async move {
+            while let Ok((mut socket, _)) = listener.accept().await {
+                counter.fetch_add(1, Ordering::SeqCst);
+                tokio::spawn(async move {
+                    let mut buf = vec![0u8; 4096];
+                    let _ = socket.read(&mut buf).await;
+                    tokio::time::sleep(delay).await;
+                    let response = format!(
+                        "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{body}",
+                        body.len()
+                    );
+                    let _ = socket.write_all(response.as_bytes()).await;
+                });
+            }
+        });
+        (format!("http://{addr}"), hits)
+    }
+
+    const BODY: &str = r#"{"name":"Hydra","symbol":"HYD","description":"test token",
+        "image":"https://ipfs.io/ipfs/img","twitter":"https://x.com/hydra","showName":true}"#;
+
+    #[test]
+    fn test_gateway_rewrite() {
+        let resolver = MetadataResolver::new("https://gw.example/".to_string());
+        assert_eq!(
+            resolver.gateway_url_for("ipfs://Qm123").as_deref(),
+            Some("https://gw.example/ipfs/Qm123")
+        );
+        assert_eq!(
+            resolver
+                .gateway_url_for("https://ipfs.io/ipfs/Qm123")
+                .as_deref(),
+            Some("https://gw.example/ipfs/Qm123")
+        );
+        assert_eq!(
+            resolver
+                .gateway_url_for("https://meta.example/a.json")
+                .as_deref(),
+            Some("https://meta.example/a.json")
+        );
+        assert_eq!(resolver.gateway_url_for("ar://abc"), None);
+    }
+
+    #[tokio::test]
+    async fn test_enrich_from_local_gateway_and_cache() {
+        let (gateway, hits) = serve(BODY, Duration::ZERO).await;
+        let resolver = MetadataResolver::new(gateway);
+        let mut signal = MintSignal::new("m".to_string(), 0.0, 0.0, 0.0, 0, 0.0, 0.0)
+            .with_uri("ipfs://QmHydra".to_string());
+
+        assert!(resolver.enrich(&mut signal).await);
+        let metadata = signal.metadata.as_ref().unwrap();
+        assert_eq!(metadata.symbol, "HYD");
+        assert_eq!(metadata.social_count(), 1);
+
+        let mut again = signal.clone();
+        again.metadata = None;
+        assert!(resolver.enrich(&mut again).await);
+        assert_eq!(hits.load(Ordering::SeqCst), 1);
+        assert_eq!(resolver.cached(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_long_description_is_truncated() {
+        let description = "é".repeat(MAX_DESCRIPTION_CHARS + 500);
+        let body = format!(r#"{{"name":"Hydra","symbol":"HYD","description":"{description}"}}"#);
+        let (gateway, _) = serve(body.leak(), Duration::ZERO).await;
+        let metadata = MetadataResolver::new(gateway)
+            .resolve("ipfs://QmLong")
+            .await
+            .unwrap();
+        let description = metadata.description.unwrap();
+        assert_eq!(description.chars().count(), MAX_DESCRIPTION_CHARS);
+        assert!(description.chars().all(|c| c == 'é'));
+    }
+
+    #[tokio::test]
+    async fn test_timeout_leaves_signal_untouched() {
+        let (gateway, _) = serve(BODY, Duration::from_millis(500)).await;
+        let resolver = MetadataResolver::new(gateway).with_timeout(Duration::from_millis(50));
+        let mut signal = MintSignal::new("m".to_string(), 0.0, 0.0, 0.0, 0, 0.0, 0.0)
+            .with_uri("ipfs://QmSlow".to_string());
+        assert!(!resolver.enrich(&mut signal).await);
+        assert!(signal.metadata.is_none());
+    }
+}
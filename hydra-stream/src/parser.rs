@@ -1,5 +1,5 @@
 use anyhow::Result;
-use hydra_core::signal::MintSignal;
+use hydra_core::signal::{MintSignal, TradeEvent, TradeKind};
 use tracing::warn;
 
 pub struct PumpfunParser;
@@ -30,7 +30,7 @@ impl PumpfunParser {
             warn!("Parsed empty mint address from pump.fun event");
         }
 
-        Ok(MintSignal::new(
+        let mut signal = MintSignal::new(
             mint_address,
             market_cap_usd,
             volume_24h_usd,
@@ -38,7 +38,50 @@ impl PumpfunParser {
             holder_count,
             liquidity_usd,
             top_holder_pct,
-        ))
+        );
+        if let Some(uri) = value["uri"].as_str().filter(|u| !u.is_empty()) {
+            signal = signal.with_uri(uri.to_string());
+        }
+        // On buys and sells the trader is not the creator
+        let creator = value["traderPublicKey"].as_str().filter(|c| !c.is_empty());
+        if let (Some("create"), Some(creator)) = (value["txType"].as_str(), creator) {
+            signal = signal.with_creator(creator.to_string());
+        }
+
+        Ok(signal)
+    }
+
+    /// Parse a pump.fun create, buy or sell event into a TradeEvent.
+    /// For creates the token amount is the creator's initial buy.
+    pub fn parse_trade(&self, raw: &[u8]) -> Result<TradeEvent> {
+        let value: serde_json::Value = serde_json::from_slice(raw)?;
+
+        let mint_address = value["mint"]
+            .as_str()
+            .ok_or_else(|| anyhow::anyhow!("missing mint field"))?
+            .to_string();
+        let trader = value["traderPublicKey"]
+            .as_str()
+            .ok_or_else(|| anyhow::anyhow!("missing traderPublicKey field"))?
+            .to_string();
+        let kind = match value["txType"].as_str() {
+            Some("create") => TradeKind::Create,
+            Some("buy") => TradeKind::Buy,
+            Some("sell") => TradeKind::Sell,
+            other => anyhow::bail!("unknown txType {:?}", other),
+        };
+        let sol_amount = value["solAmount"].as_f64().unwrap_or(0.0);
+        let token_amount = match kind {
+            TradeKind::Create => value["initialBuy"].as_f64(),
+            _ => value["tokenAmount"].as_f64(),
+        }
+        .unwrap_or(0.0);
+
+        let mut event = TradeEvent::new(mint_address, trader, kind, sol_amount, token_amount);
+        if let Some(slot) = value["slot"].as_u64() {
+            event = event.with_slot(slot);
+        }
+        Ok(event)
     }
 }
 
@@ -47,3 +90,32 @@ impl Default for PumpfunParser {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_create_and_trade() {
+        let parser = PumpfunParser::new();
+        let create = br#"{"mint":"m","txType":"create","traderPublicKey":"dev","initialBuy":1000.5,"solAmount":0.5}"#;
+        let signal = parser.parse(create).unwrap();
+        assert_eq!(signal.creator.as_deref(), Some("dev"));
+        let event = parser.parse_trade(create).unwrap();
+        assert_eq!(event.kind, TradeKind::Create);
+        assert_eq!(event.token_amount, 1000.5);
+
+        let buy = br#"{"mint":"m","txType":"buy","traderPublicKey":"buyer","tokenAmount":20.0,"solAmount":0.1}"#;
+        assert_eq!(parser.parse(buy).unwrap().creator, None);
+        assert_eq!(parser.parse_trade(buy).unwrap().trader, "buyer");
+
+        let sell = br#"{"mint":"m","txType":"sell","traderPublicKey":"dev","tokenAmount":20.0,"solAmount":0.1}"#;
+        let event = parser.parse_trade(sell).unwrap();
+        assert_eq!(event.kind, TradeKind::Sell);
+        assert_eq!(event.trader, "dev");
+        assert_eq!(event.slot, None);
+        assert!(parser
+            .parse_trade(br#"{"mint":"m","traderPublicKey":"x"}"#)
+            .is_err());
+    }
+}